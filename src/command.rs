@@ -1,47 +1,206 @@
 //! Display commands.
+//!
+//! [`Command`] describes a single ST7565 command. Commands can be encoded into the bytes
+//! that get sent to the display, and raw command bytes can be decoded back into commands,
+//! for example to make captured bus traffic readable:
+//!
+//! ```rust
+//! # use st7565::command::{Command, Decoder};
+//! let bytes = [0b10101111, 0b10000001, 0b00011111];
+//!
+//! let mut commands = Decoder::new(&bytes);
+//! assert_eq!(commands.next(), Some(Ok(Command::DisplayOnOff { on: true })));
+//! assert_eq!(
+//!     commands.next(),
+//!     Some(Ok(Command::ElectronicVolumeSet { volume_value: 0b011111 }))
+//! );
+//! assert_eq!(commands.next(), None);
+//! ```
 
-use display_interface::{DataFormat::U8, DisplayError, WriteOnlyDataCommand};
+#[cfg(feature = "async")]
+use display_interface::AsyncWriteOnlyDataCommand;
+use display_interface::{
+    DataFormat::{self, U8},
+    DisplayError, WriteOnlyDataCommand,
+};
 
-use crate::types::{BoosterRatio, PowerControlMode, StaticIndicatorMode};
+use crate::types::{
+    BoosterRatio, Controller, PowerControlMode, StaticIndicatorMode, TemperatureCompensation,
+};
 
 /// Commands
-#[derive(Debug, Copy, Clone)]
-pub(crate) enum Command {
-    DisplayOnOff { on: bool },
-    DisplayStartLineSet { address: u8 },
-    PageAddressSet { address: u8 },
-    ColumnAddressSet { address: u8 },
-    AdcSelect { reverse: bool },
-    DisplayNormalReverse { reverse: bool },
-    DisplayAllPoints { on: bool },
-    LcdBiasSet { bias_mode_1: bool },
-    //Reset,
-    CommonOutputModeSelect { reverse: bool },
-    PowerControlSet { mode: PowerControlMode },
-    VoltageRegulatorInternalResistorSet { resistor_ratio: u8 },
-    ElectronicVolumeSet { volume_value: u8 },
-    StaticIndicatorSet { mode: Option<StaticIndicatorMode> },
-    BoosterRatioSet { stepup_value: BoosterRatio },
-    //NOP,
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum Command {
+    /// Turns the display on or off
+    DisplayOnOff {
+        /// Whether the display is on
+        on: bool,
+    },
+    /// Sets the RAM line that gets displayed at the top of the display
+    DisplayStartLineSet {
+        /// The start line, in the range `0..=63`, or `0..=127` on the ST7571
+        address: u8,
+    },
+    /// Sets the RAM page for data transfers
+    PageAddressSet {
+        /// The page, in the range `0..=15`
+        address: u8,
+    },
+    /// Sets the RAM column for data transfers
+    ///
+    /// Encoded as the two commands for the upper and lower nibble.
+    ColumnAddressSet {
+        /// The column
+        address: u8,
+    },
+    /// Selects the mapping of RAM columns to segment drivers
+    AdcSelect {
+        /// Whether the mapping is reversed
+        reverse: bool,
+    },
+    /// Inverts the display
+    DisplayNormalReverse {
+        /// Whether the display is inverted
+        reverse: bool,
+    },
+    /// Turns all pixels on, regardless of the RAM content
+    DisplayAllPoints {
+        /// Whether all pixels are forced on
+        on: bool,
+    },
+    /// Selects the LCD bias
+    LcdBiasSet {
+        /// Whether bias mode "1" is selected
+        bias_mode_1: bool,
+    },
+    /// Starts the read-modify-write mode.
+    ///
+    /// While it is active, reading display data does not increment the column address,
+    /// only writing does.
+    ReadModifyWrite,
+    /// Ends the read-modify-write mode and restores the column address
+    /// from before it was started
+    End,
+    /// Resets the internal registers
+    Reset,
+    /// Selects the scan direction of the common drivers
+    CommonOutputModeSelect {
+        /// Whether the scan direction is reversed
+        reverse: bool,
+    },
+    /// Configures the internal power supply circuits
+    PowerControlSet {
+        /// The circuits that are active
+        mode: PowerControlMode,
+    },
+    /// Sets the ratio of the internal voltage regulator resistors
+    VoltageRegulatorInternalResistorSet {
+        /// The ratio, in the range `0..=7`
+        resistor_ratio: u8,
+    },
+    /// Sets the contrast
+    ElectronicVolumeSet {
+        /// The contrast, in the range `0..=63`
+        volume_value: u8,
+    },
+    /// Configures the static indicator
+    StaticIndicatorSet {
+        /// The indicator mode, or `None` to disable the indicator
+        mode: Option<StaticIndicatorMode>,
+    },
+    /// Sets the booster ratio
+    BoosterRatioSet {
+        /// The booster ratio
+        stepup_value: BoosterRatio,
+    },
+    /// Configures the advanced program control of the UC1701
+    AdvancedProgramControl {
+        /// The temperature compensation of the LCD voltage
+        temperature_compensation: TemperatureCompensation,
+        /// Whether the column address wraps around to the next page
+        column_wrap: bool,
+        /// Whether the page address wraps around after the last page
+        page_wrap: bool,
+    },
+    /// Starts the internal oscillator of the ST7571
+    OscillatorOn,
+    /// Selects between the black and white and the 4-level grayscale mode of the ST7571
+    DisplayModeSet {
+        /// Whether the grayscale mode is selected
+        grayscale: bool,
+    },
+    /// Sets a gray level register of the ST7571
+    ///
+    /// Every gray level has two registers, which hold the PWM levels of two frames each.
+    GrayLevelSet {
+        /// The register, in the range `0..=7`; from white to black
+        register: u8,
+        /// The PWM levels of both frames, one per nibble
+        value: u8,
+    },
+    /// Does nothing
+    Nop,
 }
 
-pub(crate) trait SendSt7565Command {
-    fn send_command(&mut self, command: Command) -> Result<(), DisplayError>;
+/// The reasons why command bytes could not be decoded
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum DecodeError {
+    /// No bytes were given
+    Empty,
+    /// The byte is not the start of a known command
+    UnknownCommand {
+        /// The unknown byte
+        byte: u8,
+    },
+    /// A command is missing one of its argument bytes
+    Truncated {
+        /// The first byte of the command
+        command: u8,
+    },
+    /// An argument byte of a command is invalid
+    InvalidArgument {
+        /// The first byte of the command
+        command: u8,
+        /// The invalid argument byte
+        argument: u8,
+    },
 }
 
-impl<T> SendSt7565Command for T
-where
-    T: WriteOnlyDataCommand,
-{
-    fn send_command(&mut self, command: Command) -> Result<(), DisplayError> {
+/// The encoded bytes of a single command
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct EncodedCommand {
+    bytes: [u8; MAX_COMMAND_LEN],
+    len: usize,
+}
+
+/// The maximum number of bytes of a single command
+pub(crate) const MAX_COMMAND_LEN: usize = 3;
+
+impl EncodedCommand {
+    /// The bytes of the command
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes[..self.len]
+    }
+}
+
+impl Command {
+    /// Encodes the command into the bytes that get sent to the display.
+    ///
+    /// Does not check whether a controller supports the command;
+    /// use [`encode_for()`](Self::encode_for) for that.
+    pub fn encode(self) -> EncodedCommand {
         enum Code {
             Single(u8),
             Double(u8, u8),
+            Triple(u8, u8, u8),
         }
 
         use Code::*;
 
-        let data = match command {
+        let data = match self {
             Command::DisplayOnOff { on } => Single(0b10101110 | on as u8),
             Command::DisplayStartLineSet { address } => Single(0b01000000 | (address & 0b00111111)),
             Command::PageAddressSet { address } => Single(0b10110000 | (address & 0b00001111)),
@@ -53,7 +212,9 @@ where
             Command::DisplayNormalReverse { reverse } => Single(0b10100110 | reverse as u8),
             Command::DisplayAllPoints { on } => Single(0b10100100 | on as u8),
             Command::LcdBiasSet { bias_mode_1 } => Single(0b10100010 | bias_mode_1 as u8),
-            //Command::Reset => Single(0b11100010),
+            Command::ReadModifyWrite => Single(0b11100000),
+            Command::End => Single(0b11101110),
+            Command::Reset => Single(0b11100010),
             Command::CommonOutputModeSelect { reverse } => {
                 Single(0b11000000 | ((reverse as u8) << 3))
             }
@@ -87,12 +248,492 @@ where
                     BoosterRatio::StepUp6x => 0b00000011,
                 },
             ),
-            //Command::NOP => Single(0b11100011),
+            Command::AdvancedProgramControl {
+                temperature_compensation,
+                column_wrap,
+                page_wrap,
+            } => Double(
+                0b11111010,
+                ((temperature_compensation == TemperatureCompensation::High) as u8) << 7
+                    | 0b00010000
+                    | (column_wrap as u8) << 1
+                    | page_wrap as u8,
+            ),
+            Command::OscillatorOn => Single(0b10101011),
+            // Selected through the extension command set 3
+            Command::DisplayModeSet { grayscale } => {
+                Triple(0b01111011, 0b00010000 | !grayscale as u8, 0b00000000)
+            }
+            Command::GrayLevelSet { register, value } => {
+                Double(0b10001000 | (register & 0b00000111), value)
+            }
+            Command::Nop => Single(0b11100011),
         };
 
         match data {
-            Single(val) => self.send_commands(U8(&[val])),
-            Double(val1, val2) => self.send_commands(U8(&[val1, val2])),
+            Single(val) => EncodedCommand {
+                bytes: [val, 0, 0],
+                len: 1,
+            },
+            Double(val1, val2) => EncodedCommand {
+                bytes: [val1, val2, 0],
+                len: 2,
+            },
+            Triple(val1, val2, val3) => EncodedCommand {
+                bytes: [val1, val2, val3],
+                len: 3,
+            },
         }
     }
+
+    /// Encodes the command for the given controller.
+    ///
+    /// Returns `None` if the controller does not support the command.
+    pub fn encode_for(self, controller: Controller) -> Option<EncodedCommand> {
+        let supported = match self {
+            Command::StaticIndicatorSet { .. } => {
+                matches!(controller, Controller::St7565 | Controller::S6b1713)
+            }
+            Command::BoosterRatioSet { stepup_value } => match controller {
+                Controller::St7565 => true,
+                Controller::St7567 => stepup_value != BoosterRatio::StepUp6x,
+                Controller::Uc1701 | Controller::S6b1713 | Controller::St7571 => false,
+            },
+            Command::LcdBiasSet { .. } => controller != Controller::St7571,
+            Command::AdvancedProgramControl { .. } => controller == Controller::Uc1701,
+            Command::OscillatorOn
+            | Command::DisplayModeSet { .. }
+            | Command::GrayLevelSet { .. } => controller == Controller::St7571,
+            _ => true,
+        };
+        if !supported {
+            return None;
+        }
+
+        Some(match (controller, self) {
+            // The ST7571 has 128 lines, so the start line takes a second byte
+            (Controller::St7571, Command::DisplayStartLineSet { address }) => EncodedCommand {
+                bytes: [0b01000000, address & 0b01111111, 0],
+                len: 2,
+            },
+            _ => self.encode(),
+        })
+    }
+
+    /// Decodes the first command of the given bytes.
+    ///
+    /// Returns the command and the number of bytes it occupied.
+    ///
+    /// Decoding is the exact inverse of [`encode()`](Self::encode); bytes that
+    /// [`encode()`](Self::encode) would never produce are reported as errors.
+    /// Commands whose encoding depends on the controller, like the start line and
+    /// the display mode of the ST7571, can only be decoded with
+    /// [`decode_for()`](Self::decode_for).
+    pub fn decode(bytes: &[u8]) -> Result<(Command, usize), DecodeError> {
+        let (&first, rest) = bytes.split_first().ok_or(DecodeError::Empty)?;
+
+        let second = || {
+            rest.first()
+                .copied()
+                .ok_or(DecodeError::Truncated { command: first })
+        };
+        let invalid = |argument| DecodeError::InvalidArgument {
+            command: first,
+            argument,
+        };
+
+        let command = match first {
+            0b10101110 | 0b10101111 => Command::DisplayOnOff { on: first & 1 != 0 },
+            0b01000000..=0b01111111 => Command::DisplayStartLineSet {
+                address: first & 0b00111111,
+            },
+            0b10110000..=0b10111111 => Command::PageAddressSet {
+                address: first & 0b00001111,
+            },
+            0b00010000..=0b00011111 => {
+                let second = second()?;
+                if second & 0b11110000 != 0 {
+                    return Err(invalid(second));
+                }
+                return Ok((
+                    Command::ColumnAddressSet {
+                        address: ((first & 0b00001111) << 4) | second,
+                    },
+                    2,
+                ));
+            }
+            0b10100000 | 0b10100001 => Command::AdcSelect {
+                reverse: first & 1 != 0,
+            },
+            0b10100110 | 0b10100111 => Command::DisplayNormalReverse {
+                reverse: first & 1 != 0,
+            },
+            0b10100100 | 0b10100101 => Command::DisplayAllPoints { on: first & 1 != 0 },
+            0b10100010 | 0b10100011 => Command::LcdBiasSet {
+                bias_mode_1: first & 1 != 0,
+            },
+            0b11100000 => Command::ReadModifyWrite,
+            0b11101110 => Command::End,
+            0b11100010 => Command::Reset,
+            0b11000000 | 0b11001000 => Command::CommonOutputModeSelect {
+                reverse: first & 0b00001000 != 0,
+            },
+            0b00101000..=0b00101111 => Command::PowerControlSet {
+                mode: PowerControlMode {
+                    booster_circuit: first & 0b100 != 0,
+                    voltage_regulator_circuit: first & 0b010 != 0,
+                    voltage_follower_circuit: first & 0b001 != 0,
+                },
+            },
+            0b00100000..=0b00100111 => Command::VoltageRegulatorInternalResistorSet {
+                resistor_ratio: first & 0b00000111,
+            },
+            0b10000001 => {
+                let second = second()?;
+                if second & 0b11000000 != 0 {
+                    return Err(invalid(second));
+                }
+                return Ok((
+                    Command::ElectronicVolumeSet {
+                        volume_value: second,
+                    },
+                    2,
+                ));
+            }
+            0b10101100 => Command::StaticIndicatorSet { mode: None },
+            0b10101101 => {
+                let mode = match second()? {
+                    0b00 => StaticIndicatorMode::Off,
+                    0b01 => StaticIndicatorMode::BlinkSlow,
+                    0b10 => StaticIndicatorMode::BlinkFast,
+                    0b11 => StaticIndicatorMode::On,
+                    other => return Err(invalid(other)),
+                };
+                return Ok((Command::StaticIndicatorSet { mode: Some(mode) }, 2));
+            }
+            0b11111000 => {
+                let stepup_value = match second()? {
+                    0b00000000 => BoosterRatio::StepUp2x3x4x,
+                    0b00000001 => BoosterRatio::StepUp5x,
+                    0b00000011 => BoosterRatio::StepUp6x,
+                    other => return Err(invalid(other)),
+                };
+                return Ok((Command::BoosterRatioSet { stepup_value }, 2));
+            }
+            0b11111010 => {
+                let second = second()?;
+                if second & 0b01111100 != 0b00010000 {
+                    return Err(invalid(second));
+                }
+                let temperature_compensation = match second & 0b10000000 {
+                    0 => TemperatureCompensation::Low,
+                    _ => TemperatureCompensation::High,
+                };
+                return Ok((
+                    Command::AdvancedProgramControl {
+                        temperature_compensation,
+                        column_wrap: second & 0b10 != 0,
+                        page_wrap: second & 0b01 != 0,
+                    },
+                    2,
+                ));
+            }
+            0b10101011 => Command::OscillatorOn,
+            0b10001000..=0b10001111 => {
+                return Ok((
+                    Command::GrayLevelSet {
+                        register: first & 0b00000111,
+                        value: second()?,
+                    },
+                    2,
+                ))
+            }
+            0b11100011 => Command::Nop,
+            byte => return Err(DecodeError::UnknownCommand { byte }),
+        };
+
+        Ok((command, 1))
+    }
+
+    /// Decodes the first command of the given bytes, as they were sent to the given controller.
+    ///
+    /// Returns the command and the number of bytes it occupied.
+    ///
+    /// Decoding is the exact inverse of [`encode_for()`](Self::encode_for); commands
+    /// that the controller does not support are reported as [`DecodeError::UnknownCommand`].
+    pub fn decode_for(
+        controller: Controller,
+        bytes: &[u8],
+    ) -> Result<(Command, usize), DecodeError> {
+        let argument = |index: usize| {
+            bytes
+                .get(index)
+                .copied()
+                .ok_or(DecodeError::Truncated { command: bytes[0] })
+        };
+        let invalid = |argument| DecodeError::InvalidArgument {
+            command: bytes[0],
+            argument,
+        };
+
+        let (command, len) = match (controller, bytes.first()) {
+            (Controller::St7571, Some(0b01000000)) => {
+                let address = argument(1)?;
+                if address & 0b10000000 != 0 {
+                    return Err(invalid(address));
+                }
+                (Command::DisplayStartLineSet { address }, 2)
+            }
+            (Controller::St7571, Some(0b01111011)) => {
+                let mode = argument(1)?;
+                if mode & 0b11111110 != 0b00010000 {
+                    return Err(invalid(mode));
+                }
+                match argument(2)? {
+                    0b00000000 => {}
+                    other => return Err(invalid(other)),
+                }
+                let grayscale = mode & 1 == 0;
+                (Command::DisplayModeSet { grayscale }, 3)
+            }
+            _ => Self::decode(bytes)?,
+        };
+
+        match command.encode_for(controller) {
+            Some(encoded) if encoded.as_bytes() == &bytes[..len] => Ok((command, len)),
+            _ => Err(DecodeError::UnknownCommand { byte: bytes[0] }),
+        }
+    }
+}
+
+/// Decodes a stream of command bytes into [`Command`]s.
+///
+/// Stops after the first error.
+#[derive(Debug, Clone)]
+pub struct Decoder<'a> {
+    bytes: &'a [u8],
+    controller: Option<Controller>,
+}
+
+impl<'a> Decoder<'a> {
+    /// Creates a decoder for the given command bytes, see [`Command::decode()`]
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self {
+            bytes,
+            controller: None,
+        }
+    }
+
+    /// Creates a decoder for the command bytes sent to the given controller,
+    /// see [`Command::decode_for()`]
+    pub fn new_for(controller: Controller, bytes: &'a [u8]) -> Self {
+        Self {
+            bytes,
+            controller: Some(controller),
+        }
+    }
+
+    /// The bytes that have not been decoded yet
+    pub fn remaining(&self) -> &'a [u8] {
+        self.bytes
+    }
+}
+
+impl Iterator for Decoder<'_> {
+    type Item = Result<Command, DecodeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.bytes.is_empty() {
+            return None;
+        }
+
+        let decoded = match self.controller {
+            Some(controller) => Command::decode_for(controller, self.bytes),
+            None => Command::decode(self.bytes),
+        };
+
+        Some(match decoded {
+            Ok((command, len)) => {
+                self.bytes = &self.bytes[len..];
+                Ok(command)
+            }
+            Err(e) => {
+                self.bytes = &[];
+                Err(e)
+            }
+        })
+    }
+}
+
+/// The maximum number of commands of a sequence that get sent in a single transfer
+const SEQUENCE_CHUNK_SIZE: usize = 8;
+
+/// Encodes a command for the given controller.
+///
+/// Commands that the controller does not support are reported as
+/// [`DisplayError::InvalidFormatError`].
+fn encode(controller: Controller, command: Command) -> Result<EncodedCommand, DisplayError> {
+    command
+        .encode_for(controller)
+        .ok_or(DisplayError::InvalidFormatError)
+}
+
+/// Encodes up to [`SEQUENCE_CHUNK_SIZE`] commands into a single transfer.
+///
+/// Returns the buffer and the number of bytes used.
+fn encode_chunk(
+    controller: Controller,
+    commands: &[Command],
+) -> Result<([u8; SEQUENCE_CHUNK_SIZE * MAX_COMMAND_LEN], usize), DisplayError> {
+    let mut buffer = [0; SEQUENCE_CHUNK_SIZE * MAX_COMMAND_LEN];
+    let mut len = 0;
+    for &command in commands {
+        let encoded = encode(controller, command)?;
+        let bytes = encoded.as_bytes();
+        buffer[len..len + bytes.len()].copy_from_slice(bytes);
+        len += bytes.len();
+    }
+    Ok((buffer, len))
+}
+
+pub(crate) trait SendSt7565Command {
+    fn send_command(
+        &mut self,
+        controller: Controller,
+        command: Command,
+    ) -> Result<(), DisplayError>;
+
+    /// Sends multiple commands in a single transfer, which allows the interface
+    /// to send them in a single bus transaction
+    fn send_command_sequence(
+        &mut self,
+        controller: Controller,
+        commands: &[Command],
+    ) -> Result<(), DisplayError>;
+
+    /// Sends display data to the given RAM column of a page.
+    ///
+    /// The page address is only sent if `page` is given; consecutive transfers
+    /// to the same page only have to set the column.
+    fn send_page_data(
+        &mut self,
+        controller: Controller,
+        page: Option<u8>,
+        column: u8,
+        data: DataFormat<'_>,
+    ) -> Result<(), DisplayError>;
+}
+
+/// The commands that set the address of a page transfer,
+/// and the index of the first one that has to be sent
+fn page_data_address(page: Option<u8>, column: u8) -> ([Command; 2], usize) {
+    let commands = [
+        Command::PageAddressSet {
+            address: page.unwrap_or_default(),
+        },
+        Command::ColumnAddressSet { address: column },
+    ];
+    (commands, usize::from(page.is_none()))
+}
+
+impl<T> SendSt7565Command for T
+where
+    T: WriteOnlyDataCommand,
+{
+    fn send_command(
+        &mut self,
+        controller: Controller,
+        command: Command,
+    ) -> Result<(), DisplayError> {
+        self.send_commands(U8(encode(controller, command)?.as_bytes()))
+    }
+
+    fn send_command_sequence(
+        &mut self,
+        controller: Controller,
+        commands: &[Command],
+    ) -> Result<(), DisplayError> {
+        for chunk in commands.chunks(SEQUENCE_CHUNK_SIZE) {
+            let (buffer, len) = encode_chunk(controller, chunk)?;
+            self.send_commands(U8(&buffer[..len]))?;
+        }
+        Ok(())
+    }
+
+    fn send_page_data(
+        &mut self,
+        controller: Controller,
+        page: Option<u8>,
+        column: u8,
+        data: DataFormat<'_>,
+    ) -> Result<(), DisplayError> {
+        let (commands, first) = page_data_address(page, column);
+        self.send_command_sequence(controller, &commands[first..])?;
+        self.send_data(data)
+    }
+}
+
+#[cfg(feature = "async")]
+pub(crate) trait SendSt7565CommandAsync {
+    async fn send_command(
+        &mut self,
+        controller: Controller,
+        command: Command,
+    ) -> Result<(), DisplayError>;
+
+    /// See [`SendSt7565Command::send_command_sequence()`]
+    async fn send_command_sequence(
+        &mut self,
+        controller: Controller,
+        commands: &[Command],
+    ) -> Result<(), DisplayError>;
+
+    /// See [`SendSt7565Command::send_page_data()`]
+    async fn send_page_data(
+        &mut self,
+        controller: Controller,
+        page: Option<u8>,
+        column: u8,
+        data: DataFormat<'_>,
+    ) -> Result<(), DisplayError>;
+}
+
+#[cfg(feature = "async")]
+impl<T> SendSt7565CommandAsync for T
+where
+    T: AsyncWriteOnlyDataCommand,
+{
+    async fn send_command(
+        &mut self,
+        controller: Controller,
+        command: Command,
+    ) -> Result<(), DisplayError> {
+        self.send_commands(U8(encode(controller, command)?.as_bytes()))
+            .await
+    }
+
+    async fn send_command_sequence(
+        &mut self,
+        controller: Controller,
+        commands: &[Command],
+    ) -> Result<(), DisplayError> {
+        for chunk in commands.chunks(SEQUENCE_CHUNK_SIZE) {
+            let (buffer, len) = encode_chunk(controller, chunk)?;
+            self.send_commands(U8(&buffer[..len])).await?;
+        }
+        Ok(())
+    }
+
+    async fn send_page_data(
+        &mut self,
+        controller: Controller,
+        page: Option<u8>,
+        column: u8,
+        data: DataFormat<'_>,
+    ) -> Result<(), DisplayError> {
+        let (commands, first) = page_data_address(page, column);
+        self.send_command_sequence(controller, &commands[first..])
+            .await?;
+        self.send_data(data).await
+    }
 }
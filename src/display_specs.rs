@@ -1,4 +1,4 @@
-use crate::types::{BoosterRatio, PowerControlMode};
+use crate::types::{BoosterRatio, Controller, PowerControlMode, TemperatureCompensation};
 
 /// Represents a display specification
 ///
@@ -34,8 +34,24 @@ pub trait DisplaySpecs<const WIDTH: usize, const HEIGHT: usize, const PAGES: usi
     const ELECTRONIC_VOLUME: u8;
 
     /// The internal booster ratio
+    ///
+    /// Ignored on controllers without a configurable booster ratio.
     const BOOSTER_RATIO: BoosterRatio;
 
     /// The offset of the first column on the display
     const COLUMN_OFFSET: u8;
+
+    /// The common output that the first row of the display is connected to
+    ///
+    /// Only required for displays whose first row is not connected to the
+    /// first common output.
+    const ROW_OFFSET: u8 = 0;
+
+    /// The controller chip of the display
+    const CONTROLLER: Controller = Controller::St7565;
+
+    /// The temperature compensation of the LCD voltage
+    ///
+    /// Only used on controllers that support it.
+    const TEMPERATURE_COMPENSATION: TemperatureCompensation = TemperatureCompensation::Low;
 }
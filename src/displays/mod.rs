@@ -1,7 +1,7 @@
 //! Specifications for specific displays
 
 use crate::{
-    types::{BoosterRatio, PowerControlMode},
+    types::{BoosterRatio, Controller, PowerControlMode, TemperatureCompensation},
     DisplaySpecs,
 };
 
@@ -59,3 +59,228 @@ impl DisplaySpecs<132, 64, 8> for GMG12864 {
     const BOOSTER_RATIO: BoosterRatio = BoosterRatio::StepUp2x3x4x;
     const COLUMN_OFFSET: u8 = 0;
 }
+
+/// Display specification for the DOGM128-6 display
+#[allow(non_camel_case_types)]
+pub struct DOGM128_6;
+impl DisplaySpecs<128, 64, 8> for DOGM128_6 {
+    const FLIP_ROWS: bool = false;
+    const FLIP_COLUMNS: bool = true;
+    const INVERTED: bool = false;
+    const BIAS_MODE_1: bool = false;
+    const POWER_CONTROL: PowerControlMode = PowerControlMode {
+        booster_circuit: true,
+        voltage_regulator_circuit: true,
+        voltage_follower_circuit: true,
+    };
+    const VOLTAGE_REGULATOR_RESISTOR_RATIO: u8 = 0b111;
+    const ELECTRONIC_VOLUME: u8 = 0b010110;
+    const BOOSTER_RATIO: BoosterRatio = BoosterRatio::StepUp2x3x4x;
+    const COLUMN_OFFSET: u8 = 0;
+}
+
+/// Display specification for the DOGL128-6 display, powered by the internal booster
+/// from a single 3.3V supply
+#[allow(non_camel_case_types)]
+pub struct DOGL128_6;
+impl DisplaySpecs<128, 64, 8> for DOGL128_6 {
+    const FLIP_ROWS: bool = true;
+    const FLIP_COLUMNS: bool = false;
+    const INVERTED: bool = false;
+    const BIAS_MODE_1: bool = false;
+    const POWER_CONTROL: PowerControlMode = PowerControlMode {
+        booster_circuit: true,
+        voltage_regulator_circuit: true,
+        voltage_follower_circuit: true,
+    };
+    const VOLTAGE_REGULATOR_RESISTOR_RATIO: u8 = 0b111;
+    const ELECTRONIC_VOLUME: u8 = 0b010110;
+    const BOOSTER_RATIO: BoosterRatio = BoosterRatio::StepUp2x3x4x;
+    const COLUMN_OFFSET: u8 = 4;
+}
+
+/// Display specification for the Adafruit ST7565 128x64 display, which uses an LM6059 panel.
+///
+/// The first row of the panel is connected to common output 32. The Adafruit library
+/// reverses the common output direction, but also the page order and bit order,
+/// so the rows are not flipped.
+pub struct LM6059;
+impl DisplaySpecs<128, 64, 8> for LM6059 {
+    const FLIP_ROWS: bool = false;
+    const FLIP_COLUMNS: bool = false;
+    const INVERTED: bool = false;
+    const BIAS_MODE_1: bool = true;
+    const POWER_CONTROL: PowerControlMode = PowerControlMode {
+        booster_circuit: true,
+        voltage_regulator_circuit: true,
+        voltage_follower_circuit: true,
+    };
+    const VOLTAGE_REGULATOR_RESISTOR_RATIO: u8 = 0b110;
+    const ELECTRONIC_VOLUME: u8 = 0b011000;
+    const BOOSTER_RATIO: BoosterRatio = BoosterRatio::StepUp2x3x4x;
+    const COLUMN_OFFSET: u8 = 1;
+    const ROW_OFFSET: u8 = 32;
+}
+
+/// Display specification for the Newhaven NHD-C12832A1Z display
+#[allow(non_camel_case_types)]
+pub struct NHD_C12832A1Z;
+impl DisplaySpecs<128, 32, 4> for NHD_C12832A1Z {
+    const FLIP_ROWS: bool = true;
+    const FLIP_COLUMNS: bool = false;
+    const INVERTED: bool = false;
+    const BIAS_MODE_1: bool = false;
+    const POWER_CONTROL: PowerControlMode = PowerControlMode {
+        booster_circuit: true,
+        voltage_regulator_circuit: true,
+        voltage_follower_circuit: true,
+    };
+    const VOLTAGE_REGULATOR_RESISTOR_RATIO: u8 = 0b010;
+    const ELECTRONIC_VOLUME: u8 = 0b010111;
+    const BOOSTER_RATIO: BoosterRatio = BoosterRatio::StepUp2x3x4x;
+    const COLUMN_OFFSET: u8 = 0;
+}
+
+/// Display specification for the Newhaven NHD-C12864A1Z display
+#[allow(non_camel_case_types)]
+pub struct NHD_C12864;
+impl DisplaySpecs<128, 64, 8> for NHD_C12864 {
+    const FLIP_ROWS: bool = true;
+    const FLIP_COLUMNS: bool = false;
+    const INVERTED: bool = false;
+    const BIAS_MODE_1: bool = false;
+    const POWER_CONTROL: PowerControlMode = PowerControlMode {
+        booster_circuit: true,
+        voltage_regulator_circuit: true,
+        voltage_follower_circuit: true,
+    };
+    const VOLTAGE_REGULATOR_RESISTOR_RATIO: u8 = 0b110;
+    const ELECTRONIC_VOLUME: u8 = 0b010001;
+    const BOOSTER_RATIO: BoosterRatio = BoosterRatio::StepUp2x3x4x;
+    const COLUMN_OFFSET: u8 = 0;
+}
+
+/// Display specification for the JLX12864G-086 display
+pub struct JLX12864;
+impl DisplaySpecs<128, 64, 8> for JLX12864 {
+    const FLIP_ROWS: bool = true;
+    const FLIP_COLUMNS: bool = false;
+    const INVERTED: bool = false;
+    const BIAS_MODE_1: bool = false;
+    const POWER_CONTROL: PowerControlMode = PowerControlMode {
+        booster_circuit: true,
+        voltage_regulator_circuit: true,
+        voltage_follower_circuit: true,
+    };
+    const VOLTAGE_REGULATOR_RESISTOR_RATIO: u8 = 0b011;
+    const ELECTRONIC_VOLUME: u8 = 0b101000;
+    const BOOSTER_RATIO: BoosterRatio = BoosterRatio::StepUp2x3x4x;
+    const COLUMN_OFFSET: u8 = 0;
+}
+
+/// Display specification for the ERC12864-1 display
+pub struct ERC12864;
+impl DisplaySpecs<128, 64, 8> for ERC12864 {
+    const FLIP_ROWS: bool = false;
+    const FLIP_COLUMNS: bool = true;
+    const INVERTED: bool = false;
+    const BIAS_MODE_1: bool = false;
+    const POWER_CONTROL: PowerControlMode = PowerControlMode {
+        booster_circuit: true,
+        voltage_regulator_circuit: true,
+        voltage_follower_circuit: true,
+    };
+    const VOLTAGE_REGULATOR_RESISTOR_RATIO: u8 = 0b011;
+    const ELECTRONIC_VOLUME: u8 = 0b100111;
+    const BOOSTER_RATIO: BoosterRatio = BoosterRatio::StepUp2x3x4x;
+    const COLUMN_OFFSET: u8 = 0;
+}
+
+/// Display specification for 128x64 modules with an ST7567A controller, like the ones
+/// connected through I2C.
+///
+/// Use it together with the [`I2cInterface`](crate::interface::i2c::I2cInterface).
+#[allow(non_camel_case_types)]
+pub struct ST7567A_12864;
+impl DisplaySpecs<128, 64, 8> for ST7567A_12864 {
+    const FLIP_ROWS: bool = true;
+    const FLIP_COLUMNS: bool = false;
+    const INVERTED: bool = false;
+    const BIAS_MODE_1: bool = false;
+    const POWER_CONTROL: PowerControlMode = PowerControlMode {
+        booster_circuit: true,
+        voltage_regulator_circuit: true,
+        voltage_follower_circuit: true,
+    };
+    const VOLTAGE_REGULATOR_RESISTOR_RATIO: u8 = 0b101;
+    const ELECTRONIC_VOLUME: u8 = 0b100000;
+    const BOOSTER_RATIO: BoosterRatio = BoosterRatio::StepUp2x3x4x;
+    const COLUMN_OFFSET: u8 = 0;
+    const CONTROLLER: Controller = Controller::St7567;
+}
+
+/// Display specification for the DOGS102-6 display, which uses a UC1701X controller.
+///
+/// Without an `A0` pin, it can be connected through the
+/// [`Spi9BitInterface`](crate::interface::spi_9bit::Spi9BitInterface).
+#[allow(non_camel_case_types)]
+pub struct DOGS102_6;
+impl DisplaySpecs<102, 64, 8> for DOGS102_6 {
+    const FLIP_ROWS: bool = false;
+    const FLIP_COLUMNS: bool = true;
+    const INVERTED: bool = false;
+    const BIAS_MODE_1: bool = false;
+    const POWER_CONTROL: PowerControlMode = PowerControlMode {
+        booster_circuit: true,
+        voltage_regulator_circuit: true,
+        voltage_follower_circuit: true,
+    };
+    const VOLTAGE_REGULATOR_RESISTOR_RATIO: u8 = 0b111;
+    const ELECTRONIC_VOLUME: u8 = 0b010000;
+    const BOOSTER_RATIO: BoosterRatio = BoosterRatio::StepUp2x3x4x;
+    const COLUMN_OFFSET: u8 = 0;
+    const CONTROLLER: Controller = Controller::Uc1701;
+    const TEMPERATURE_COMPENSATION: TemperatureCompensation = TemperatureCompensation::High;
+}
+
+/// Display specification for generic 128x64 displays with an S6B1713, KS0713 or NT7534 controller.
+#[allow(non_camel_case_types)]
+pub struct S6B1713_12864;
+impl DisplaySpecs<128, 64, 8> for S6B1713_12864 {
+    const FLIP_ROWS: bool = true;
+    const FLIP_COLUMNS: bool = false;
+    const INVERTED: bool = false;
+    const BIAS_MODE_1: bool = false;
+    const POWER_CONTROL: PowerControlMode = PowerControlMode {
+        booster_circuit: true,
+        voltage_regulator_circuit: true,
+        voltage_follower_circuit: true,
+    };
+    const VOLTAGE_REGULATOR_RESISTOR_RATIO: u8 = 0b100;
+    const ELECTRONIC_VOLUME: u8 = 0b100000;
+    const BOOSTER_RATIO: BoosterRatio = BoosterRatio::StepUp2x3x4x;
+    const COLUMN_OFFSET: u8 = 0;
+    const CONTROLLER: Controller = Controller::S6b1713;
+}
+
+/// Display specification for generic 128x128 displays with an ST7571 controller.
+///
+/// Supports the [`GrayscaleMode`](crate::modes::GrayscaleMode).
+#[allow(non_camel_case_types)]
+pub struct ST7571_128128;
+impl DisplaySpecs<128, 128, 16> for ST7571_128128 {
+    const FLIP_ROWS: bool = true;
+    const FLIP_COLUMNS: bool = false;
+    const INVERTED: bool = false;
+    const BIAS_MODE_1: bool = false;
+    const POWER_CONTROL: PowerControlMode = PowerControlMode {
+        booster_circuit: true,
+        voltage_regulator_circuit: true,
+        voltage_follower_circuit: true,
+    };
+    const VOLTAGE_REGULATOR_RESISTOR_RATIO: u8 = 0b111;
+    const ELECTRONIC_VOLUME: u8 = 0b110100;
+    const BOOSTER_RATIO: BoosterRatio = BoosterRatio::StepUp2x3x4x;
+    const COLUMN_OFFSET: u8 = 0;
+    const CONTROLLER: Controller = Controller::St7571;
+}
@@ -0,0 +1,196 @@
+use display_interface::{AsyncWriteOnlyDataCommand, DataFormat::U8, DisplayError};
+use embedded_hal::digital::OutputPin;
+use embedded_hal_async::delay::DelayNs;
+
+use super::{
+    super::common_functionality::{icon_page, SettingsChange, RAM_COLUMNS},
+    AsyncInterface,
+};
+use crate::{
+    command::{Command, SendSt7565CommandAsync},
+    types::{
+        BoosterRatio, Contrast, LcdBias, PowerControlMode, RegulatorRatio, StaticIndicatorMode,
+    },
+    DisplaySpecs, Error, ST7565,
+};
+
+/// ---- Common functionality for all async modes ----
+/// ==================================================
+impl<DI, SPECS, MODE, const WIDTH: usize, const HEIGHT: usize, const PAGES: usize>
+    ST7565<AsyncInterface<DI>, SPECS, MODE, WIDTH, HEIGHT, PAGES>
+where
+    DI: AsyncWriteOnlyDataCommand,
+    SPECS: DisplaySpecs<WIDTH, HEIGHT, PAGES>,
+{
+    /// Set the static indicator
+    ///
+    /// Fails with [`DisplayError::InvalidFormatError`] on controllers without a static indicator.
+    pub async fn set_static_indicator(
+        &mut self,
+        mode: Option<StaticIndicatorMode>,
+    ) -> Result<(), DisplayError> {
+        self.apply_change(self.static_indicator_change(mode)).await
+    }
+
+    /// Sets the line offset, effectively scrolling the display through memory.
+    pub async fn set_line_offset(&mut self, offset: u8) -> Result<(), DisplayError> {
+        self.apply_change(self.line_offset_change(offset)).await
+    }
+
+    /// Sets whether the pixels should be inverted.
+    pub async fn set_inverted(&mut self, inverted: bool) -> Result<(), DisplayError> {
+        self.apply_change(self.inverted_change(inverted)).await
+    }
+
+    /// Displays all points of the display
+    ///
+    /// While the display is in a power save state, the value is only stored
+    /// and gets applied by [`wake()`](Self::wake).
+    pub async fn display_all_points(&mut self, enable: bool) -> Result<(), DisplayError> {
+        self.apply_change(self.all_points_change(enable)).await
+    }
+
+    /// Enable/Disable the display output
+    ///
+    /// While the display is in a power save state, the value is only stored
+    /// and gets applied by [`wake()`](Self::wake).
+    pub async fn set_display_on(&mut self, on: bool) -> Result<(), DisplayError> {
+        self.apply_change(self.display_on_change(on)).await
+    }
+
+    /// Puts the display into sleep mode.
+    ///
+    /// Turns off the display output, the static indicator and the internal power circuits.
+    /// The display data is retained and can still be modified, for example through a flush.
+    ///
+    /// Call [`wake()`](Self::wake) to resume normal operation.
+    pub async fn enter_sleep(&mut self) -> Result<(), DisplayError> {
+        self.apply_change(self.power_save_change(None)).await
+    }
+
+    /// Puts the display into standby mode.
+    ///
+    /// Same as [`enter_sleep()`](Self::enter_sleep), except that the static indicator
+    /// stays active with the given mode.
+    ///
+    /// Fails with [`DisplayError::InvalidFormatError`] on controllers without a static indicator.
+    pub async fn enter_standby(
+        &mut self,
+        static_indicator: StaticIndicatorMode,
+    ) -> Result<(), DisplayError> {
+        self.apply_change(self.power_save_change(Some(static_indicator)))
+            .await
+    }
+
+    /// Prepares the display for removing its supply voltage.
+    ///
+    /// Enters sleep mode and then shuts down the internal power circuits,
+    /// starting with the booster.
+    ///
+    /// Call [`wake()`](Self::wake) to resume normal operation if the supply voltage was kept,
+    /// or [`reset()`](Self::reset) after it was restored.
+    pub async fn power_off(&mut self) -> Result<(), DisplayError> {
+        self.apply_change(self.power_save_change(None)).await?;
+        self.apply_change(self.power_down_change()).await
+    }
+
+    /// Leaves the power save mode.
+    ///
+    /// Restores the configured power circuits and the display on/off and all points states.
+    /// The static indicator is not restored.
+    pub async fn wake(&mut self) -> Result<(), DisplayError> {
+        self.apply_change(self.wake_change()).await
+    }
+
+    /// Sets the contrast of the display.
+    ///
+    /// The value is kept across [`reset()`](Self::reset) calls.
+    pub async fn set_contrast(&mut self, contrast: Contrast) -> Result<(), DisplayError> {
+        self.apply_change(self.contrast_change(contrast)).await
+    }
+
+    /// Sets the ratio of the internal voltage regulator resistors.
+    ///
+    /// The value is kept across [`reset()`](Self::reset) calls.
+    pub async fn set_regulator_ratio(&mut self, ratio: RegulatorRatio) -> Result<(), DisplayError> {
+        self.apply_change(self.regulator_ratio_change(ratio)).await
+    }
+
+    /// Sets the LCD bias mode.
+    ///
+    /// The value is kept across [`reset()`](Self::reset) calls.
+    pub async fn set_bias(&mut self, bias: LcdBias) -> Result<(), DisplayError> {
+        self.apply_change(self.bias_change(bias)).await
+    }
+
+    /// Sets the ratio of the internal booster.
+    ///
+    /// The value is kept across [`reset()`](Self::reset) calls.
+    /// Fails with [`DisplayError::InvalidFormatError`] if the controller does not support the ratio.
+    pub async fn set_booster_ratio(&mut self, ratio: BoosterRatio) -> Result<(), DisplayError> {
+        self.apply_change(self.booster_ratio_change(ratio)).await
+    }
+
+    /// Enables/Disables the internal power circuits.
+    ///
+    /// The value is kept across [`reset()`](Self::reset) calls.
+    /// While the display is in a power save state, the value is only stored
+    /// and gets applied by [`wake()`](Self::wake).
+    pub async fn set_power_control(&mut self, mode: PowerControlMode) -> Result<(), DisplayError> {
+        self.apply_change(self.power_control_change(mode)).await
+    }
+
+    /// Reset the display and restore all settings
+    pub async fn reset<RST, DELAY, PinE>(
+        &mut self,
+        rst: &mut RST,
+        delay: &mut DELAY,
+    ) -> Result<(), Error<PinE>>
+    where
+        RST: OutputPin<Error = PinE>,
+        DELAY: DelayNs,
+    {
+        // Reset display
+        rst.set_low().map_err(Error::Pin)?;
+        delay.delay_ms(1).await;
+        rst.set_high().map_err(Error::Pin)?;
+        delay.delay_ms(1).await;
+
+        // Initialize display
+        let change = self.reset_change();
+        for command in change.commands {
+            self.interface
+                .0
+                .send_command(SPECS::CONTROLLER, command)
+                .await
+                .map_err(Error::Comm)?;
+        }
+        if let Some(page) = icon_page(SPECS::CONTROLLER) {
+            self.interface
+                .0
+                .send_page_data(SPECS::CONTROLLER, Some(page), 0, U8(&[0; RAM_COLUMNS]))
+                .await
+                .map_err(Error::Comm)?;
+        }
+        self.settings = change.settings;
+
+        Ok(())
+    }
+
+    /// Sends the commands of a settings change and then stores its settings
+    ///
+    /// See [`apply_change()`](ST7565::apply_change) of the blocking driver.
+    pub(super) async fn apply_change(
+        &mut self,
+        change: SettingsChange<impl IntoIterator<Item = Command>>,
+    ) -> Result<(), DisplayError> {
+        for command in change.commands {
+            self.interface
+                .0
+                .send_command(SPECS::CONTROLLER, command)
+                .await?;
+        }
+        self.settings = change.settings;
+        Ok(())
+    }
+}
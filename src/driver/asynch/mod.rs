@@ -0,0 +1,15 @@
+mod common_functionality;
+mod mode_graphics;
+mod mode_grayscale;
+mod mode_initial;
+mod mode_page_band;
+mod mode_raw;
+mod mode_terminal;
+
+/// Wraps an [`AsyncWriteOnlyDataCommand`](display_interface::AsyncWriteOnlyDataCommand) display interface.
+///
+/// A driver that holds this wrapper provides `async` versions of all functions
+/// that communicate with the display.
+///
+/// Created by [`ST7565::new_async()`](crate::ST7565::new_async).
+pub struct AsyncInterface<DI>(pub(crate) DI);
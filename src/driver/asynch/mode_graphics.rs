@@ -0,0 +1,120 @@
+use display_interface::{AsyncWriteOnlyDataCommand, DataFormat::U8, DisplayError};
+
+use super::AsyncInterface;
+use crate::{
+    command::SendSt7565CommandAsync, modes::GraphicsMode, types::Orientation, DisplaySpecs,
+    FlushError, ST7565,
+};
+
+/// ---- Functionality of the async graphics mode ----
+/// ==================================================
+///
+/// In this mode, the driver can be used as a [DrawTarget](embedded_graphics_core::draw_target::DrawTarget) for the [embedded_graphics](embedded_graphics_core) crate.
+impl<
+        'a,
+        DI: AsyncWriteOnlyDataCommand,
+        SPECS: DisplaySpecs<WIDTH, HEIGHT, PAGES>,
+        const WIDTH: usize,
+        const HEIGHT: usize,
+        const PAGES: usize,
+    > ST7565<AsyncInterface<DI>, SPECS, GraphicsMode<'a, WIDTH, PAGES>, WIDTH, HEIGHT, PAGES>
+{
+    /// Flushes the internal buffer to the screen.
+    ///
+    /// Needs to be called after drawing to actually display the data on screen.
+    ///
+    /// See [`flush()`](ST7565::flush) of the blocking driver.
+    pub async fn flush(&mut self) -> Result<(), FlushError> {
+        let column_offset = self.column_offset();
+
+        if let Some(line_offset) = self.mode.hidden_line_offset() {
+            for transfer in self.mode.double_buffered_transfers(column_offset) {
+                self.interface
+                    .0
+                    .send_page_data(
+                        SPECS::CONTROLLER,
+                        transfer.address,
+                        transfer.column,
+                        U8(transfer.data),
+                    )
+                    .await
+                    .map_err(|error| transfer.error(error))?;
+            }
+
+            let result = self.set_line_offset(line_offset).await;
+            return self.mode.finish_swap(result);
+        }
+
+        for index in 0..PAGES {
+            let mut result = Ok(());
+            for transfer in self.mode.page_transfers(index, column_offset) {
+                result = self
+                    .interface
+                    .0
+                    .send_page_data(
+                        SPECS::CONTROLLER,
+                        transfer.address,
+                        transfer.column,
+                        U8(transfer.data),
+                    )
+                    .await;
+                if result.is_err() {
+                    break;
+                }
+            }
+            self.mode.finish_page(index, result)?;
+        }
+
+        Ok(())
+    }
+
+    /// Rotates the display by 180 degrees in hardware.
+    ///
+    /// See [`set_orientation()`](ST7565::set_orientation) of the blocking driver.
+    pub async fn set_orientation(&mut self, orientation: Orientation) -> Result<(), DisplayError> {
+        self.apply_change(self.orientation_change(orientation))
+            .await?;
+        self.mode.mark_dirty();
+        Ok(())
+    }
+
+    /// Release the display interface object
+    ///
+    /// See [`release_display_interface()`](ST7565::release_display_interface) of the blocking driver.
+    pub fn release_display_interface(
+        self,
+    ) -> (
+        ST7565<(), SPECS, GraphicsMode<'a, WIDTH, PAGES>, WIDTH, HEIGHT, PAGES>,
+        DI,
+    ) {
+        (
+            ST7565 {
+                interface: (),
+                display_specs: self.display_specs,
+                mode: self.mode,
+                settings: self.settings,
+            },
+            self.interface.0,
+        )
+    }
+}
+
+/// ---- Functionality of the detached graphics mode ----
+/// =====================================================
+impl<'a, SPECS, const WIDTH: usize, const HEIGHT: usize, const PAGES: usize>
+    ST7565<(), SPECS, GraphicsMode<'a, WIDTH, PAGES>, WIDTH, HEIGHT, PAGES>
+{
+    /// Attach an asynchronous display interface back to the driver
+    pub fn attach_async_display_interface<DI: AsyncWriteOnlyDataCommand>(
+        self,
+        interface: DI,
+    ) -> ST7565<AsyncInterface<DI>, SPECS, GraphicsMode<'a, WIDTH, PAGES>, WIDTH, HEIGHT, PAGES>
+    {
+        ST7565 {
+            interface: AsyncInterface(interface),
+            display_specs: self.display_specs,
+            mode: self.mode,
+            settings: self.settings,
+        }
+    }
+}
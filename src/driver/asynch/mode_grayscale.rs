@@ -0,0 +1,58 @@
+use display_interface::{AsyncWriteOnlyDataCommand, DataFormat::U8Iter, DisplayError};
+use embedded_graphics_core::pixelcolor::Gray2;
+
+use super::{super::mode_grayscale::gray_level_commands, AsyncInterface};
+use crate::{
+    command::SendSt7565CommandAsync, modes::GrayscaleMode, DisplaySpecs, FlushError, ST7565,
+};
+
+/// ---- Functionality of the async grayscale mode ----
+/// ===================================================
+///
+/// In this mode, the driver can be used as a grayscale [DrawTarget](embedded_graphics_core::draw_target::DrawTarget) for the [embedded_graphics](embedded_graphics_core) crate.
+impl<
+        'a,
+        DI: AsyncWriteOnlyDataCommand,
+        SPECS: DisplaySpecs<WIDTH, HEIGHT, PAGES>,
+        const WIDTH: usize,
+        const HEIGHT: usize,
+        const PAGES: usize,
+    > ST7565<AsyncInterface<DI>, SPECS, GrayscaleMode<'a, WIDTH, PAGES>, WIDTH, HEIGHT, PAGES>
+{
+    /// Flushes the internal buffer to the screen.
+    ///
+    /// Needs to be called after drawing to actually display the data on screen.
+    ///
+    /// See [`flush()`](ST7565::flush) of the blocking driver.
+    pub async fn flush(&mut self) -> Result<(), FlushError> {
+        let column_offset = self.column_offset();
+
+        for index in 0..PAGES {
+            for mut transfer in self.mode.page_transfers(index, column_offset) {
+                self.interface
+                    .0
+                    .send_page_data(
+                        SPECS::CONTROLLER,
+                        transfer.address,
+                        transfer.column,
+                        U8Iter(&mut transfer.data),
+                    )
+                    .await
+                    .map_err(|error| transfer.error(error))?;
+            }
+            self.mode.finish_page(index);
+        }
+
+        Ok(())
+    }
+
+    /// Sets the brightness of a gray level.
+    ///
+    /// See [`set_gray_level()`](ST7565::set_gray_level) of the blocking driver.
+    pub async fn set_gray_level(&mut self, color: Gray2, pwm: u8) -> Result<(), DisplayError> {
+        self.interface
+            .0
+            .send_command_sequence(SPECS::CONTROLLER, &gray_level_commands(color, pwm))
+            .await
+    }
+}
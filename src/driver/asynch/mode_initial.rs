@@ -0,0 +1,25 @@
+use core::marker::PhantomData;
+
+use display_interface::AsyncWriteOnlyDataCommand;
+
+use super::{super::DisplaySettings, AsyncInterface};
+use crate::{modes::InitialMode, DisplaySpecs, ST7565};
+
+/// ---- Functionality of the async initial mode ----
+/// =================================================
+impl<DI, SPECS, const WIDTH: usize, const HEIGHT: usize, const PAGES: usize>
+    ST7565<AsyncInterface<DI>, SPECS, InitialMode, WIDTH, HEIGHT, PAGES>
+where
+    DI: AsyncWriteOnlyDataCommand,
+    SPECS: DisplaySpecs<WIDTH, HEIGHT, PAGES>,
+{
+    /// Creates an ST7565 driver that communicates with the display asynchronously.
+    pub fn new_async(interface: DI, _display_specs: SPECS) -> Self {
+        Self {
+            interface: AsyncInterface(interface),
+            display_specs: PhantomData,
+            mode: InitialMode,
+            settings: DisplaySettings::from_specs::<SPECS, WIDTH, HEIGHT, PAGES>(),
+        }
+    }
+}
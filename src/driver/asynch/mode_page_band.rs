@@ -0,0 +1,54 @@
+use display_interface::{AsyncWriteOnlyDataCommand, DataFormat::U8};
+
+use super::AsyncInterface;
+use crate::{
+    command::SendSt7565CommandAsync, modes::PageBandMode, DisplaySpecs, FlushError, PageBand,
+    ST7565,
+};
+
+/// ---- Functionality of the async page band mode ----
+/// ===================================================
+///
+/// In this mode, every frame gets rendered band by band through a draw closure.
+impl<
+        'a,
+        DI,
+        SPECS,
+        const WIDTH: usize,
+        const HEIGHT: usize,
+        const PAGES: usize,
+        const BAND_PAGES: usize,
+    > ST7565<AsyncInterface<DI>, SPECS, PageBandMode<'a, WIDTH, BAND_PAGES>, WIDTH, HEIGHT, PAGES>
+where
+    DI: AsyncWriteOnlyDataCommand,
+    SPECS: DisplaySpecs<WIDTH, HEIGHT, PAGES>,
+{
+    /// Renders a frame and sends it to the display.
+    ///
+    /// See [`render()`](ST7565::render) of the blocking driver.
+    pub async fn render<F>(&mut self, mut draw: F) -> Result<(), FlushError>
+    where
+        F: FnMut(&mut PageBand<'_, WIDTH, HEIGHT, BAND_PAGES>),
+    {
+        let column_offset = self.column_offset();
+
+        for first_page in (0..PAGES).step_by(BAND_PAGES) {
+            draw(&mut self.mode.band(first_page));
+
+            for transfer in self.mode.band_transfers::<PAGES>(first_page, column_offset) {
+                self.interface
+                    .0
+                    .send_page_data(
+                        SPECS::CONTROLLER,
+                        transfer.address,
+                        transfer.column,
+                        U8(transfer.data),
+                    )
+                    .await
+                    .map_err(|error| transfer.error(error))?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,63 @@
+use display_interface::{AsyncWriteOnlyDataCommand, DataFormat::U8, DisplayError};
+
+use super::AsyncInterface;
+use crate::command::{Command, SendSt7565CommandAsync};
+use crate::{modes::RawMode, DisplaySpecs, ST7565};
+
+/// ---- Functionality of the async raw mode ----
+/// =============================================
+///
+/// This mode exists for interacting with the ST7565 chip via direct low level commands.
+impl<DI, SPECS, const WIDTH: usize, const HEIGHT: usize, const PAGES: usize>
+    ST7565<AsyncInterface<DI>, SPECS, RawMode, WIDTH, HEIGHT, PAGES>
+where
+    DI: AsyncWriteOnlyDataCommand,
+    SPECS: DisplaySpecs<WIDTH, HEIGHT, PAGES>,
+{
+    /// Sets the page to write into
+    pub async fn set_page(&mut self, page: u8) -> Result<(), DisplayError> {
+        self.interface
+            .0
+            .send_command(SPECS::CONTROLLER, Command::PageAddressSet { address: page })
+            .await
+    }
+
+    /// Sets the column to write into
+    pub async fn set_column(&mut self, address: u8) -> Result<(), DisplayError> {
+        self.interface
+            .0
+            .send_command(SPECS::CONTROLLER, Command::ColumnAddressSet { address })
+            .await
+    }
+
+    /// Writes raw pixel data.
+    ///
+    /// For more information how data is processed by the display, read the
+    /// ST7565 reference manual.
+    pub async fn write_pixel_data(&mut self, data: &[u8]) -> Result<(), DisplayError> {
+        self.interface.0.send_data(U8(data)).await
+    }
+
+    /// Sets the adc direction.
+    ///
+    /// Effectively flips the display horizontally.
+    pub async fn adc_select(&mut self, reverse: bool) -> Result<(), DisplayError> {
+        self.interface
+            .0
+            .send_command(SPECS::CONTROLLER, Command::AdcSelect { reverse })
+            .await
+    }
+
+    /// Sets the common mode direction.
+    ///
+    /// Effectively flips the display vertically.
+    pub async fn common_output_mode_select(&mut self, reverse: bool) -> Result<(), DisplayError> {
+        self.interface
+            .0
+            .send_command(
+                SPECS::CONTROLLER,
+                Command::CommonOutputModeSelect { reverse },
+            )
+            .await
+    }
+}
@@ -0,0 +1,83 @@
+use display_interface::{AsyncWriteOnlyDataCommand, DataFormat::U8, DisplayError};
+
+use super::AsyncInterface;
+use crate::{
+    command::SendSt7565CommandAsync,
+    driver::{common_functionality::ram_pages, font::glyph},
+    modes::TerminalMode,
+    DisplaySpecs, ST7565,
+};
+
+/// ---- Functionality of the async terminal mode ----
+/// ==================================================
+///
+/// In this mode, the driver acts as a text console.
+impl<DI, SPECS, const WIDTH: usize, const HEIGHT: usize, const PAGES: usize>
+    ST7565<AsyncInterface<DI>, SPECS, TerminalMode, WIDTH, HEIGHT, PAGES>
+where
+    DI: AsyncWriteOnlyDataCommand,
+    SPECS: DisplaySpecs<WIDTH, HEIGHT, PAGES>,
+{
+    /// Clears the display and moves the cursor to the top left corner.
+    ///
+    /// See [`clear()`](ST7565::clear) of the blocking driver.
+    pub async fn clear(&mut self) -> Result<(), DisplayError> {
+        self.mode = TerminalMode::new(ram_pages(SPECS::CONTROLLER));
+        for page in 0..self.mode.ram_pages {
+            self.clear_page(page).await?;
+        }
+        self.set_line_offset(0).await
+    }
+
+    /// Prints the given text at the cursor position.
+    ///
+    /// See [`print()`](ST7565::print) of the blocking driver.
+    pub async fn print(&mut self, text: &str) -> Result<(), DisplayError> {
+        for c in text.chars() {
+            match c {
+                '\n' => self.newline().await?,
+                '\r' => self.mode.carriage_return(),
+                c => self.print_char(c).await?,
+            }
+        }
+        Ok(())
+    }
+
+    async fn print_char(&mut self, c: char) -> Result<(), DisplayError> {
+        if self.mode.column >= Self::COLUMNS {
+            self.newline().await?;
+        }
+
+        if let Some(commands) = self.cursor_address() {
+            self.interface
+                .0
+                .send_command_sequence(SPECS::CONTROLLER, &commands)
+                .await?;
+        }
+        self.interface.0.send_data(U8(&glyph(c))).await?;
+        self.mode.advance();
+
+        Ok(())
+    }
+
+    async fn newline(&mut self) -> Result<(), DisplayError> {
+        if let Some((page, line_offset)) = self.mode.newline(Self::ROWS) {
+            self.clear_page(page).await?;
+            self.set_line_offset(line_offset).await?;
+        }
+        Ok(())
+    }
+
+    async fn clear_page(&mut self, page: u8) -> Result<(), DisplayError> {
+        self.mode.address_valid = false;
+        self.interface
+            .0
+            .send_page_data(
+                SPECS::CONTROLLER,
+                Some(page),
+                self.column_offset(),
+                U8(&[0; WIDTH]),
+            )
+            .await
+    }
+}
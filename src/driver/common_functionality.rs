@@ -1,13 +1,71 @@
-use display_interface::{DisplayError, WriteOnlyDataCommand};
+use core::ops::Range;
+
+use display_interface::{DataFormat::U8, DisplayError, WriteOnlyDataCommand};
 use embedded_hal::{delay::DelayNs, digital::OutputPin};
 
 use crate::{
     command::{Command, SendSt7565Command},
-    types::StaticIndicatorMode,
-    DisplaySpecs, Error,
+    interface::ReadableDataCommand,
+    types::{
+        BoosterRatio, Contrast, Controller, LcdBias, Orientation, PowerControlMode, PowerState,
+        RegulatorRatio, StaticIndicatorMode, Status,
+    },
+    DisplaySpecs, Error, HealthCheckError,
 };
 
-use super::ST7565;
+use super::{settings::DisplaySettings, ST7565};
+
+/// The number of columns of the display RAM
+pub(super) const RAM_COLUMNS: usize = 132;
+/// The number of columns of the ST7571 display RAM
+const ST7571_RAM_COLUMNS: usize = 128;
+/// The number of lines of the display RAM that can be displayed
+const COMMON_LINES: usize = 64;
+/// The number of lines of the ST7571 display RAM that can be displayed
+const ST7571_COMMON_LINES: usize = 128;
+
+/// The number of lines of the display RAM of the given controller that can be displayed
+pub(super) const fn common_lines(controller: Controller) -> usize {
+    match controller {
+        Controller::St7571 => ST7571_COMMON_LINES,
+        _ => COMMON_LINES,
+    }
+}
+
+/// The number of pages of the display RAM of the given controller that can be displayed
+/// and scrolled through
+pub(super) const fn ram_pages(controller: Controller) -> u8 {
+    (common_lines(controller) / 8) as u8
+}
+
+/// The page of the display RAM that holds the icon line of the given controller, if it has one
+///
+/// The icon line is shown on the additional common output `COMS`. It is not part of the
+/// scrollable lines and is not covered by any mode, so it gets cleared on reset.
+pub(super) const fn icon_page(controller: Controller) -> Option<u8> {
+    match controller {
+        Controller::St7567 => Some(ram_pages(controller)),
+        _ => None,
+    }
+}
+
+/// Whether the register defaults of the given controller after a reset differ from the
+/// ones of the ST7565, so that all of them have to be sent during initialization
+pub(super) const fn sends_reset_defaults(controller: Controller) -> bool {
+    matches!(controller, Controller::St7567 | Controller::S6b1713)
+}
+
+/// A change of the display settings
+///
+/// Holds the commands that apply the change to the display, and the settings that are
+/// in effect once all of them were sent. Shared by the blocking and the async driver,
+/// which only differ in how they send the commands.
+pub(super) struct SettingsChange<C> {
+    /// The commands to send, in order
+    pub commands: C,
+    /// The settings after all commands were sent
+    pub settings: DisplaySettings,
+}
 
 /// ---- Common functionality for all modes ----
 /// ============================================
@@ -18,35 +76,120 @@ where
     SPECS: DisplaySpecs<WIDTH, HEIGHT, PAGES>,
 {
     /// Set the static indicator
+    ///
+    /// Fails with [`DisplayError::InvalidFormatError`] on controllers without a static indicator.
     pub fn set_static_indicator(
         &mut self,
         mode: Option<StaticIndicatorMode>,
     ) -> Result<(), DisplayError> {
-        self.interface
-            .send_command(Command::StaticIndicatorSet { mode })
+        self.apply_change(self.static_indicator_change(mode))
     }
 
     /// Sets the line offset, effectively scrolling the display through memory.
     pub fn set_line_offset(&mut self, offset: u8) -> Result<(), DisplayError> {
-        self.interface
-            .send_command(Command::DisplayStartLineSet { address: offset })
+        self.apply_change(self.line_offset_change(offset))
     }
 
     /// Sets whether the pixels should be inverted.
     pub fn set_inverted(&mut self, inverted: bool) -> Result<(), DisplayError> {
-        self.interface
-            .send_command(Command::DisplayNormalReverse { reverse: inverted })
+        self.apply_change(self.inverted_change(inverted))
     }
 
     /// Displays all points of the display
+    ///
+    /// While the display is in a power save state, the value is only stored
+    /// and gets applied by [`wake()`](Self::wake).
     pub fn display_all_points(&mut self, enable: bool) -> Result<(), DisplayError> {
-        self.interface
-            .send_command(Command::DisplayAllPoints { on: enable })
+        self.apply_change(self.all_points_change(enable))
     }
 
     /// Enable/Disable the display output
+    ///
+    /// While the display is in a power save state, the value is only stored
+    /// and gets applied by [`wake()`](Self::wake).
     pub fn set_display_on(&mut self, on: bool) -> Result<(), DisplayError> {
-        self.interface.send_command(Command::DisplayOnOff { on })
+        self.apply_change(self.display_on_change(on))
+    }
+
+    /// Puts the display into sleep mode.
+    ///
+    /// Turns off the display output, the static indicator and the internal power circuits.
+    /// The display data is retained and can still be modified, for example through a flush.
+    ///
+    /// Call [`wake()`](Self::wake) to resume normal operation.
+    pub fn enter_sleep(&mut self) -> Result<(), DisplayError> {
+        self.apply_change(self.power_save_change(None))
+    }
+
+    /// Puts the display into standby mode.
+    ///
+    /// Same as [`enter_sleep()`](Self::enter_sleep), except that the static indicator
+    /// stays active with the given mode.
+    ///
+    /// Fails with [`DisplayError::InvalidFormatError`] on controllers without a static indicator.
+    pub fn enter_standby(
+        &mut self,
+        static_indicator: StaticIndicatorMode,
+    ) -> Result<(), DisplayError> {
+        self.apply_change(self.power_save_change(Some(static_indicator)))
+    }
+
+    /// Prepares the display for removing its supply voltage.
+    ///
+    /// Enters sleep mode and then shuts down the internal power circuits,
+    /// starting with the booster.
+    ///
+    /// Call [`wake()`](Self::wake) to resume normal operation if the supply voltage was kept,
+    /// or [`reset()`](Self::reset) after it was restored.
+    pub fn power_off(&mut self) -> Result<(), DisplayError> {
+        self.apply_change(self.power_save_change(None))?;
+        self.apply_change(self.power_down_change())
+    }
+
+    /// Leaves the power save mode.
+    ///
+    /// Restores the configured power circuits and the display on/off and all points states.
+    /// The static indicator is not restored.
+    pub fn wake(&mut self) -> Result<(), DisplayError> {
+        self.apply_change(self.wake_change())
+    }
+
+    /// Sets the contrast of the display.
+    ///
+    /// The value is kept across [`reset()`](Self::reset) calls.
+    pub fn set_contrast(&mut self, contrast: Contrast) -> Result<(), DisplayError> {
+        self.apply_change(self.contrast_change(contrast))
+    }
+
+    /// Sets the ratio of the internal voltage regulator resistors.
+    ///
+    /// The value is kept across [`reset()`](Self::reset) calls.
+    pub fn set_regulator_ratio(&mut self, ratio: RegulatorRatio) -> Result<(), DisplayError> {
+        self.apply_change(self.regulator_ratio_change(ratio))
+    }
+
+    /// Sets the LCD bias mode.
+    ///
+    /// The value is kept across [`reset()`](Self::reset) calls.
+    pub fn set_bias(&mut self, bias: LcdBias) -> Result<(), DisplayError> {
+        self.apply_change(self.bias_change(bias))
+    }
+
+    /// Sets the ratio of the internal booster.
+    ///
+    /// The value is kept across [`reset()`](Self::reset) calls.
+    /// Fails with [`DisplayError::InvalidFormatError`] if the controller does not support the ratio.
+    pub fn set_booster_ratio(&mut self, ratio: BoosterRatio) -> Result<(), DisplayError> {
+        self.apply_change(self.booster_ratio_change(ratio))
+    }
+
+    /// Enables/Disables the internal power circuits.
+    ///
+    /// The value is kept across [`reset()`](Self::reset) calls.
+    /// While the display is in a power save state, the value is only stored
+    /// and gets applied by [`wake()`](Self::wake).
+    pub fn set_power_control(&mut self, mode: PowerControlMode) -> Result<(), DisplayError> {
+        self.apply_change(self.power_control_change(mode))
     }
 
     /// Reset the display and restore all settings
@@ -66,68 +209,447 @@ where
         delay.delay_ms(1);
 
         // Initialize display
+        let change = self.reset_change();
+        for command in change.commands {
+            self.interface
+                .send_command(SPECS::CONTROLLER, command)
+                .map_err(Error::Comm)?;
+        }
+        if let Some(page) = icon_page(SPECS::CONTROLLER) {
+            self.interface
+                .send_page_data(SPECS::CONTROLLER, Some(page), 0, U8(&[0; RAM_COLUMNS]))
+                .map_err(Error::Comm)?;
+        }
+        self.settings = change.settings;
 
-        // LCD Bias
-        self.interface
-            .send_command(Command::LcdBiasSet {
-                bias_mode_1: SPECS::BIAS_MODE_1,
-            })
-            .map_err(Error::Comm)?;
-
-        // ADC Selection
-        self.interface
-            .send_command(Command::AdcSelect {
-                reverse: SPECS::FLIP_COLUMNS,
-            })
-            .map_err(Error::Comm)?;
-
-        // Common output mode selection
-        self.interface
-            .send_command(Command::CommonOutputModeSelect {
-                reverse: SPECS::FLIP_ROWS,
-            })
-            .map_err(Error::Comm)?;
-
-        // Display invertion
-        self.interface
-            .send_command(Command::DisplayNormalReverse {
-                reverse: SPECS::INVERTED,
-            })
-            .map_err(Error::Comm)?;
-
-        // Booster ratio
-        self.interface
-            .send_command(Command::BoosterRatioSet {
-                stepup_value: SPECS::BOOSTER_RATIO,
-            })
-            .map_err(Error::Comm)?;
-
-        // voltage regulator resistor ratio
-        self.interface
-            .send_command(Command::VoltageRegulatorInternalResistorSet {
-                resistor_ratio: SPECS::VOLTAGE_REGULATOR_RESISTOR_RATIO,
-            })
-            .map_err(Error::Comm)?;
-
-        // electric volume
-        self.interface
-            .send_command(Command::ElectronicVolumeSet {
-                volume_value: SPECS::ELECTRONIC_VOLUME,
-            })
-            .map_err(Error::Comm)?;
-
-        // power control
-        self.interface
-            .send_command(Command::PowerControlSet {
-                mode: SPECS::POWER_CONTROL,
-            })
-            .map_err(Error::Comm)?;
-
-        // some ICs do not reset line offset to 0, so do that here as well
-        self.interface
-            .send_command(Command::DisplayStartLineSet { address: 0 })
-            .map_err(Error::Comm)?;
+        Ok(())
+    }
 
+    /// Sends the commands of a settings change and then stores its settings
+    pub(super) fn apply_change(
+        &mut self,
+        change: SettingsChange<impl IntoIterator<Item = Command>>,
+    ) -> Result<(), DisplayError> {
+        for command in change.commands {
+            self.interface.send_command(SPECS::CONTROLLER, command)?;
+        }
+        self.settings = change.settings;
         Ok(())
     }
 }
+
+/// ---- Read functionality for all modes ----
+/// ===========================================
+///
+/// Only available with a display interface that can read from the display,
+/// like the parallel 6800 and 8080 buses.
+impl<DI, SPECS, MODE, const WIDTH: usize, const HEIGHT: usize, const PAGES: usize>
+    ST7565<DI, SPECS, MODE, WIDTH, HEIGHT, PAGES>
+where
+    DI: ReadableDataCommand,
+    SPECS: DisplaySpecs<WIDTH, HEIGHT, PAGES>,
+{
+    /// Reads the status of the controller
+    pub fn read_status(&mut self) -> Result<Status, DisplayError> {
+        self.interface.read_status().map(Status::from_byte)
+    }
+
+    /// Reads the given columns of a page from the display RAM.
+    ///
+    /// Only `data[columns]` gets modified; columns outside of the display are ignored.
+    pub fn read_page(
+        &mut self,
+        page: u8,
+        columns: Range<usize>,
+        data: &mut [u8; WIDTH],
+    ) -> Result<(), DisplayError> {
+        let columns = columns.start..columns.end.min(WIDTH);
+        if columns.is_empty() {
+            return Ok(());
+        }
+
+        self.interface.send_command_sequence(
+            SPECS::CONTROLLER,
+            &[
+                Command::PageAddressSet { address: page },
+                Command::ColumnAddressSet {
+                    address: self.column_offset() + columns.start as u8,
+                },
+            ],
+        )?;
+
+        // The first read only latches the byte at the current address
+        self.interface.read_data(&mut [0])?;
+        self.interface.read_data(&mut data[columns])
+    }
+
+    /// Checks whether the controller reports the state the driver configured.
+    ///
+    /// Intended to be called after [`reset()`](Self::reset), to detect a missing or
+    /// broken display. Compares the ADC direction and the display on/off state,
+    /// and fails if the controller is busy or still resetting.
+    pub fn check_health(&mut self) -> Result<Status, HealthCheckError> {
+        let status = self.read_status().map_err(HealthCheckError::Comm)?;
+
+        let expected = Status {
+            busy: false,
+            adc_reverse: SPECS::FLIP_COLUMNS
+                != (self.settings.orientation == Orientation::UpsideDown),
+            display_on: self.settings.display_on && !self.settings.is_powered_down(),
+            resetting: false,
+        };
+
+        if status == expected {
+            Ok(status)
+        } else {
+            Err(HealthCheckError::UnexpectedStatus(status))
+        }
+    }
+}
+
+impl<DI, SPECS, MODE, const WIDTH: usize, const HEIGHT: usize, const PAGES: usize>
+    ST7565<DI, SPECS, MODE, WIDTH, HEIGHT, PAGES>
+where
+    SPECS: DisplaySpecs<WIDTH, HEIGHT, PAGES>,
+{
+    /// The current contrast of the display
+    pub fn contrast(&self) -> Contrast {
+        self.settings.contrast
+    }
+
+    /// The current ratio of the internal voltage regulator resistors
+    pub fn regulator_ratio(&self) -> RegulatorRatio {
+        self.settings.regulator_ratio
+    }
+
+    /// The current LCD bias mode
+    pub fn bias(&self) -> LcdBias {
+        self.settings.bias
+    }
+
+    /// The current ratio of the internal booster
+    pub fn booster_ratio(&self) -> BoosterRatio {
+        self.settings.booster_ratio
+    }
+
+    /// The current configuration of the internal power circuits
+    pub fn power_control(&self) -> PowerControlMode {
+        self.settings.power_control
+    }
+
+    /// The current power state of the display
+    pub fn power_state(&self) -> PowerState {
+        self.settings.power_state
+    }
+
+    /// The current hardware orientation of the display
+    pub fn orientation(&self) -> Orientation {
+        self.settings.orientation
+    }
+
+    /// The RAM column of the first display column
+    ///
+    /// In upside down orientation, the display columns are mapped to the other
+    /// end of the RAM, as the RAM is wider than most displays.
+    pub(super) fn column_offset(&self) -> u8 {
+        match self.settings.orientation {
+            Orientation::Normal => SPECS::COLUMN_OFFSET,
+            Orientation::UpsideDown => {
+                let ram_columns = match SPECS::CONTROLLER {
+                    Controller::St7571 => ST7571_RAM_COLUMNS,
+                    _ => RAM_COLUMNS,
+                };
+                ram_columns.saturating_sub(WIDTH + usize::from(SPECS::COLUMN_OFFSET)) as u8
+            }
+        }
+    }
+
+    /// The start line that displays the given line offset in the given orientation
+    ///
+    /// In upside down orientation, the lines are scanned in reverse order,
+    /// so the start line has to be moved to the other end of the displayed lines.
+    ///
+    /// The first row is connected to the common output [`ROW_OFFSET`](DisplaySpecs::ROW_OFFSET),
+    /// so the start line has to be moved back by that amount, or forward in reverse order.
+    fn start_line(line_offset: u8, orientation: Orientation) -> u8 {
+        let common_lines = common_lines(SPECS::CONTROLLER);
+        let line_offset = usize::from(line_offset);
+        let row_offset = usize::from(SPECS::ROW_OFFSET) % common_lines;
+        let start_line = match orientation {
+            Orientation::Normal => line_offset + common_lines - row_offset,
+            Orientation::UpsideDown => line_offset + HEIGHT + row_offset,
+        };
+        (start_line % common_lines) as u8
+    }
+
+    /// A change that sends the given commands and then updates the settings with `update`
+    fn change<C>(
+        &self,
+        commands: C,
+        update: impl FnOnce(&mut DisplaySettings),
+    ) -> SettingsChange<C> {
+        let mut settings = self.settings;
+        update(&mut settings);
+        SettingsChange { commands, settings }
+    }
+
+    /// A change of a value that only gets sent while the display is not in a power save state
+    fn active_change(
+        &self,
+        command: Command,
+        update: impl FnOnce(&mut DisplaySettings),
+    ) -> SettingsChange<Option<Command>> {
+        let command = (!self.settings.is_powered_down()).then_some(command);
+        self.change(command, update)
+    }
+
+    /// The change that sets the static indicator
+    pub(super) fn static_indicator_change(
+        &self,
+        mode: Option<StaticIndicatorMode>,
+    ) -> SettingsChange<[Command; 1]> {
+        self.change([Command::StaticIndicatorSet { mode }], |_| {})
+    }
+
+    /// The change that sets the line offset
+    pub(super) fn line_offset_change(&self, offset: u8) -> SettingsChange<[Command; 1]> {
+        let offset = (usize::from(offset) % common_lines(SPECS::CONTROLLER)) as u8;
+        let address = Self::start_line(offset, self.settings.orientation);
+        self.change([Command::DisplayStartLineSet { address }], |settings| {
+            settings.line_offset = offset
+        })
+    }
+
+    /// The change that sets whether the pixels are inverted
+    pub(super) fn inverted_change(&self, inverted: bool) -> SettingsChange<[Command; 1]> {
+        self.change(
+            [Command::DisplayNormalReverse { reverse: inverted }],
+            |_| {},
+        )
+    }
+
+    /// The change that displays all points of the display
+    pub(super) fn all_points_change(&self, enable: bool) -> SettingsChange<Option<Command>> {
+        self.active_change(Command::DisplayAllPoints { on: enable }, |settings| {
+            settings.all_points_on = enable
+        })
+    }
+
+    /// The change that enables/disables the display output
+    pub(super) fn display_on_change(&self, on: bool) -> SettingsChange<Option<Command>> {
+        self.active_change(Command::DisplayOnOff { on }, |settings| {
+            settings.display_on = on
+        })
+    }
+
+    /// The change that enters sleep mode, or standby mode with the given static indicator
+    pub(super) fn power_save_change(
+        &self,
+        static_indicator: Option<StaticIndicatorMode>,
+    ) -> SettingsChange<impl Iterator<Item = Command>> {
+        let indicator = Command::StaticIndicatorSet {
+            mode: static_indicator,
+        };
+        // Controllers without a static indicator do not need to turn it off
+        let indicator = (static_indicator.is_some()
+            || indicator.encode_for(SPECS::CONTROLLER).is_some())
+        .then_some(indicator);
+        let commands = indicator.into_iter().chain(IntoIterator::into_iter([
+            Command::DisplayOnOff { on: false },
+            Command::DisplayAllPoints { on: true },
+        ]));
+
+        self.change(commands, |settings| {
+            settings.power_state = match static_indicator {
+                Some(_) => PowerState::Standby,
+                None => PowerState::Sleep,
+            }
+        })
+    }
+
+    /// The change that shuts down the internal power circuits
+    pub(super) fn power_down_change(&self) -> SettingsChange<[Command; 2]> {
+        let mode = self.settings.power_control;
+        let commands = [
+            // The booster needs to be turned off first
+            Command::PowerControlSet {
+                mode: PowerControlMode {
+                    booster_circuit: false,
+                    ..mode
+                },
+            },
+            Command::PowerControlSet {
+                mode: PowerControlMode {
+                    booster_circuit: false,
+                    voltage_regulator_circuit: false,
+                    voltage_follower_circuit: false,
+                },
+            },
+        ];
+
+        self.change(commands, |settings| settings.power_state = PowerState::Off)
+    }
+
+    /// The change that leaves the power save mode
+    pub(super) fn wake_change(&self) -> SettingsChange<[Command; 3]> {
+        let settings = &self.settings;
+        let commands = [
+            Command::PowerControlSet {
+                mode: settings.power_control,
+            },
+            Command::DisplayAllPoints {
+                on: settings.all_points_on,
+            },
+            Command::DisplayOnOff {
+                on: settings.display_on,
+            },
+        ];
+
+        self.change(commands, |settings| {
+            settings.power_state = PowerState::Active
+        })
+    }
+
+    /// The change that sets the contrast
+    pub(super) fn contrast_change(&self, contrast: Contrast) -> SettingsChange<[Command; 1]> {
+        let command = Command::ElectronicVolumeSet {
+            volume_value: contrast.value(),
+        };
+        self.change([command], |settings| settings.contrast = contrast)
+    }
+
+    /// The change that sets the ratio of the internal voltage regulator resistors
+    pub(super) fn regulator_ratio_change(
+        &self,
+        ratio: RegulatorRatio,
+    ) -> SettingsChange<[Command; 1]> {
+        let command = Command::VoltageRegulatorInternalResistorSet {
+            resistor_ratio: ratio.value(),
+        };
+        self.change([command], |settings| settings.regulator_ratio = ratio)
+    }
+
+    /// The change that sets the LCD bias mode
+    pub(super) fn bias_change(&self, bias: LcdBias) -> SettingsChange<[Command; 1]> {
+        let command = Command::LcdBiasSet {
+            bias_mode_1: bias == LcdBias::Mode1,
+        };
+        self.change([command], |settings| settings.bias = bias)
+    }
+
+    /// The change that sets the ratio of the internal booster
+    pub(super) fn booster_ratio_change(&self, ratio: BoosterRatio) -> SettingsChange<[Command; 1]> {
+        let command = Command::BoosterRatioSet {
+            stepup_value: ratio,
+        };
+        self.change([command], |settings| settings.booster_ratio = ratio)
+    }
+
+    /// The change that enables/disables the internal power circuits
+    pub(super) fn power_control_change(
+        &self,
+        mode: PowerControlMode,
+    ) -> SettingsChange<Option<Command>> {
+        self.active_change(Command::PowerControlSet { mode }, |settings| {
+            settings.power_control = mode
+        })
+    }
+
+    /// The change that configures the given orientation
+    pub(super) fn orientation_change(
+        &self,
+        orientation: Orientation,
+    ) -> SettingsChange<[Command; 3]> {
+        let commands = Self::orientation_sequence(self.settings.line_offset, orientation);
+        self.change(commands, |settings| settings.orientation = orientation)
+    }
+
+    /// The commands that configure the given orientation and line offset
+    fn orientation_sequence(line_offset: u8, orientation: Orientation) -> [Command; 3] {
+        let upside_down = orientation == Orientation::UpsideDown;
+
+        [
+            Command::AdcSelect {
+                reverse: SPECS::FLIP_COLUMNS != upside_down,
+            },
+            Command::CommonOutputModeSelect {
+                reverse: SPECS::FLIP_ROWS != upside_down,
+            },
+            Command::DisplayStartLineSet {
+                address: Self::start_line(line_offset, orientation),
+            },
+        ]
+    }
+
+    /// The change that initializes the display after a hardware reset
+    ///
+    /// The display is turned off and scrolled back to the first line after a reset.
+    pub(super) fn reset_change(&self) -> SettingsChange<impl Iterator<Item = Command>> {
+        let settings = DisplaySettings {
+            line_offset: 0,
+            power_state: PowerState::Active,
+            display_on: false,
+            all_points_on: false,
+            ..self.settings
+        };
+        let controller = SPECS::CONTROLLER;
+        let [adc_select, common_output_mode_select, start_line_set] =
+            Self::orientation_sequence(settings.line_offset, settings.orientation);
+
+        let commands = IntoIterator::into_iter([
+            // The register defaults after a hardware reset differ on some controllers
+            (controller == Controller::S6b1713).then_some(Command::Reset),
+            // LCD Bias, if it is configurable
+            (controller != Controller::St7571).then_some(Command::LcdBiasSet {
+                bias_mode_1: settings.bias == LcdBias::Mode1,
+            }),
+            // ADC Selection
+            Some(adc_select),
+            // Common output mode selection
+            Some(common_output_mode_select),
+            // Display invertion
+            Some(Command::DisplayNormalReverse {
+                reverse: SPECS::INVERTED,
+            }),
+            // Booster ratio, if it is configurable
+            matches!(controller, Controller::St7565 | Controller::St7567).then_some(
+                Command::BoosterRatioSet {
+                    stepup_value: settings.booster_ratio,
+                },
+            ),
+            // voltage regulator resistor ratio
+            Some(Command::VoltageRegulatorInternalResistorSet {
+                resistor_ratio: settings.regulator_ratio.value(),
+            }),
+            // electric volume
+            Some(Command::ElectronicVolumeSet {
+                volume_value: settings.contrast.value(),
+            }),
+            // temperature compensation
+            (controller == Controller::Uc1701).then_some(Command::AdvancedProgramControl {
+                temperature_compensation: SPECS::TEMPERATURE_COMPENSATION,
+                column_wrap: false,
+                page_wrap: false,
+            }),
+            // oscillator and display mode
+            (controller == Controller::St7571).then_some(Command::OscillatorOn),
+            (controller == Controller::St7571).then_some(Command::DisplayModeSet {
+                grayscale: settings.grayscale,
+            }),
+            // power control
+            Some(Command::PowerControlSet {
+                mode: settings.power_control,
+            }),
+            // some ICs do not reset line offset to 0, so do that here as well
+            Some(start_line_set),
+            // the remaining registers that are assumed to be at their defaults
+            (controller == Controller::S6b1713)
+                .then_some(Command::StaticIndicatorSet { mode: None }),
+            sends_reset_defaults(controller).then_some(Command::DisplayAllPoints { on: false }),
+            sends_reset_defaults(controller).then_some(Command::DisplayOnOff { on: false }),
+        ])
+        .flatten();
+
+        SettingsChange { commands, settings }
+    }
+}
@@ -0,0 +1,120 @@
+//! The built-in font of the terminal mode.
+//!
+//! Generated from the public domain `5x8` font of the X11 misc-fixed family.
+
+/// The width of a character cell, including one column of spacing
+pub(crate) const CHAR_WIDTH: usize = 6;
+
+/// The columns of the glyph of the given character.
+///
+/// Each byte is one column, with the least significant bit at the top.
+/// Characters outside of printable ASCII are rendered as `?`.
+pub(crate) fn glyph(c: char) -> [u8; CHAR_WIDTH] {
+    let index = match c {
+        ' '..='~' => c as usize - ' ' as usize,
+        _ => '?' as usize - ' ' as usize,
+    };
+
+    let [a, b, c, d, e] = FONT[index];
+    [a, b, c, d, e, 0]
+}
+
+/// The glyphs of the printable ASCII characters, starting at `' '`
+#[rustfmt::skip]
+const FONT: [[u8; 5]; 95] = [
+    [0x00, 0x00, 0x00, 0x00, 0x00], // ' '
+    [0x00, 0x00, 0x5E, 0x00, 0x00], // '!'
+    [0x00, 0x0E, 0x00, 0x0E, 0x00], // '"'
+    [0x14, 0x7F, 0x14, 0x7F, 0x14], // '#'
+    [0x04, 0x2A, 0x7F, 0x2A, 0x10], // '$'
+    [0x00, 0x16, 0x08, 0x34, 0x00], // '%'
+    [0x36, 0x49, 0x36, 0x40, 0x00], // '&'
+    [0x00, 0x00, 0x0E, 0x00, 0x00], // "'"
+    [0x00, 0x3C, 0x42, 0x00, 0x00], // '('
+    [0x00, 0x42, 0x3C, 0x00, 0x00], // ')'
+    [0x54, 0x38, 0x38, 0x54, 0x00], // '*'
+    [0x10, 0x10, 0x7C, 0x10, 0x10], // '+'
+    [0x00, 0x80, 0x60, 0x20, 0x00], // ','
+    [0x10, 0x10, 0x10, 0x10, 0x00], // '-'
+    [0x00, 0x40, 0xE0, 0x40, 0x00], // '.'
+    [0x60, 0x10, 0x08, 0x06, 0x00], // '/'
+    [0x00, 0x3C, 0x42, 0x3C, 0x00], // '0'
+    [0x00, 0x44, 0x7E, 0x40, 0x00], // '1'
+    [0x64, 0x52, 0x52, 0x4C, 0x00], // '2'
+    [0x22, 0x4A, 0x4E, 0x32, 0x00], // '3'
+    [0x18, 0x14, 0x7E, 0x10, 0x00], // '4'
+    [0x2E, 0x4A, 0x4A, 0x32, 0x00], // '5'
+    [0x3C, 0x4A, 0x4A, 0x30, 0x00], // '6'
+    [0x02, 0x62, 0x1A, 0x06, 0x00], // '7'
+    [0x34, 0x4A, 0x4A, 0x34, 0x00], // '8'
+    [0x0C, 0x52, 0x52, 0x3C, 0x00], // '9'
+    [0x00, 0x6C, 0x6C, 0x00, 0x00], // ':'
+    [0x00, 0x80, 0x6C, 0x2C, 0x00], // ';'
+    [0x00, 0x18, 0x24, 0x42, 0x00], // '<'
+    [0x28, 0x28, 0x28, 0x28, 0x00], // '='
+    [0x00, 0x42, 0x24, 0x18, 0x00], // '>'
+    [0x00, 0x04, 0x52, 0x0C, 0x00], // '?'
+    [0x3C, 0x42, 0x99, 0xA5, 0x1E], // '@'
+    [0x7C, 0x12, 0x12, 0x7C, 0x00], // 'A'
+    [0x7E, 0x4A, 0x4A, 0x34, 0x00], // 'B'
+    [0x3C, 0x42, 0x42, 0x24, 0x00], // 'C'
+    [0x7E, 0x42, 0x42, 0x3C, 0x00], // 'D'
+    [0x7E, 0x4A, 0x4A, 0x42, 0x00], // 'E'
+    [0x7E, 0x0A, 0x0A, 0x02, 0x00], // 'F'
+    [0x3C, 0x42, 0x52, 0x34, 0x00], // 'G'
+    [0x7E, 0x08, 0x08, 0x7E, 0x00], // 'H'
+    [0x00, 0x42, 0x7E, 0x42, 0x00], // 'I'
+    [0x20, 0x42, 0x3E, 0x02, 0x00], // 'J'
+    [0x7E, 0x08, 0x34, 0x42, 0x00], // 'K'
+    [0x7E, 0x40, 0x40, 0x40, 0x00], // 'L'
+    [0x7E, 0x0C, 0x0C, 0x7E, 0x00], // 'M'
+    [0x7E, 0x0C, 0x38, 0x7E, 0x00], // 'N'
+    [0x3C, 0x42, 0x42, 0x3C, 0x00], // 'O'
+    [0x7E, 0x12, 0x12, 0x0C, 0x00], // 'P'
+    [0x3C, 0x52, 0x62, 0xBC, 0x00], // 'Q'
+    [0x7E, 0x12, 0x12, 0x6C, 0x00], // 'R'
+    [0x24, 0x4A, 0x52, 0x24, 0x00], // 'S'
+    [0x00, 0x02, 0x7E, 0x02, 0x00], // 'T'
+    [0x3E, 0x40, 0x40, 0x3E, 0x00], // 'U'
+    [0x1E, 0x60, 0x60, 0x1E, 0x00], // 'V'
+    [0x7E, 0x30, 0x30, 0x7E, 0x00], // 'W'
+    [0x66, 0x18, 0x18, 0x66, 0x00], // 'X'
+    [0x06, 0x08, 0x70, 0x08, 0x06], // 'Y'
+    [0x62, 0x52, 0x4A, 0x46, 0x00], // 'Z'
+    [0x00, 0x7E, 0x42, 0x42, 0x00], // '['
+    [0x06, 0x08, 0x10, 0x60, 0x00], // '\\'
+    [0x00, 0x42, 0x42, 0x7E, 0x00], // ']'
+    [0x00, 0x04, 0x02, 0x04, 0x00], // '^'
+    [0x80, 0x80, 0x80, 0x80, 0x00], // '_'
+    [0x00, 0x02, 0x04, 0x00, 0x00], // '`'
+    [0x30, 0x48, 0x48, 0x78, 0x00], // 'a'
+    [0x7E, 0x48, 0x48, 0x30, 0x00], // 'b'
+    [0x00, 0x30, 0x48, 0x48, 0x00], // 'c'
+    [0x30, 0x48, 0x48, 0x7E, 0x00], // 'd'
+    [0x30, 0x68, 0x58, 0x10, 0x00], // 'e'
+    [0x10, 0x7C, 0x12, 0x04, 0x00], // 'f'
+    [0x10, 0xA8, 0xA8, 0x70, 0x00], // 'g'
+    [0x7E, 0x08, 0x08, 0x70, 0x00], // 'h'
+    [0x00, 0x48, 0x7A, 0x40, 0x00], // 'i'
+    [0x00, 0x40, 0x80, 0x7A, 0x00], // 'j'
+    [0x7E, 0x10, 0x10, 0x68, 0x00], // 'k'
+    [0x00, 0x42, 0x7E, 0x40, 0x00], // 'l'
+    [0x78, 0x08, 0x70, 0x08, 0x70], // 'm'
+    [0x78, 0x08, 0x08, 0x70, 0x00], // 'n'
+    [0x30, 0x48, 0x48, 0x30, 0x00], // 'o'
+    [0xF8, 0x28, 0x28, 0x10, 0x00], // 'p'
+    [0x10, 0x28, 0x28, 0xF8, 0x00], // 'q'
+    [0x78, 0x10, 0x08, 0x10, 0x00], // 'r'
+    [0x00, 0x50, 0x58, 0x28, 0x00], // 's'
+    [0x08, 0x3E, 0x48, 0x20, 0x00], // 't'
+    [0x38, 0x40, 0x40, 0x78, 0x00], // 'u'
+    [0x00, 0x38, 0x40, 0x38, 0x00], // 'v'
+    [0x38, 0x40, 0x30, 0x40, 0x38], // 'w'
+    [0x48, 0x30, 0x30, 0x48, 0x00], // 'x'
+    [0x58, 0xA0, 0xA0, 0x78, 0x00], // 'y'
+    [0x48, 0x68, 0x58, 0x48, 0x00], // 'z'
+    [0x08, 0x2A, 0x55, 0x41, 0x00], // '{'
+    [0x00, 0x00, 0x7E, 0x00, 0x00], // '|'
+    [0x41, 0x55, 0x2A, 0x08, 0x00], // '}'
+    [0x04, 0x02, 0x04, 0x02, 0x00], // '~'
+];
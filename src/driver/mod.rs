@@ -1,18 +1,32 @@
+#[cfg(feature = "async")]
+mod asynch;
 mod common_functionality;
+mod font;
 
+pub mod mode_direct_graphics;
 pub mod mode_graphics;
+pub mod mode_grayscale;
 pub mod mode_initial;
+pub mod mode_page_band;
 pub mod mode_raw;
+pub mod mode_terminal;
 
 mod page_buffer;
+mod settings;
 
 use core::marker::PhantomData;
 
-pub use page_buffer::GraphicsPageBuffer;
+#[cfg(feature = "async")]
+pub use asynch::AsyncInterface;
+pub use mode_page_band::PageBand;
+pub use page_buffer::{GraphicsPageBuffer, GrayscalePageBuffer, PageBandBuffer, ShadowBuffer};
+
+use settings::DisplaySettings;
 
 /// The actual driver
 pub struct ST7565<DI, SPECS, MODE, const WIDTH: usize, const HEIGHT: usize, const PAGES: usize> {
     interface: DI,
     display_specs: PhantomData<SPECS>,
     mode: MODE,
+    settings: DisplaySettings,
 }
@@ -0,0 +1,152 @@
+use display_interface::{DataFormat::U8, DisplayError};
+use embedded_graphics_core::{
+    draw_target::DrawTarget,
+    geometry::{OriginDimensions, Point, Size},
+    pixelcolor::BinaryColor,
+    Pixel,
+};
+
+use crate::{
+    command::{Command, SendSt7565Command},
+    interface::ReadableDataCommand,
+    DisplaySpecs, ST7565,
+};
+
+/// In this mode, the driver can be used as a [DrawTarget] for the [embedded_graphics](embedded_graphics_core) crate
+/// without any buffer.
+///
+/// Every pixel gets drawn directly into the display RAM, by reading the byte that contains it,
+/// modifying it and writing it back. Requires a [`ReadableDataCommand`] display interface.
+pub struct DirectGraphicsMode {
+    /// The page and column that the next read-modify-write access goes to,
+    /// or `None` if the read-modify-write mode is not active
+    pub(crate) cursor: Option<(u8, u8)>,
+}
+
+impl DirectGraphicsMode {
+    pub(crate) fn new() -> Self {
+        Self { cursor: None }
+    }
+}
+
+/// ---- Functionality of the direct graphics mode ----
+/// ===================================================
+///
+/// In this mode, the driver can be used as a bufferless [DrawTarget] for the [embedded_graphics](embedded_graphics_core) crate.
+impl<DI, SPECS, const WIDTH: usize, const HEIGHT: usize, const PAGES: usize>
+    ST7565<DI, SPECS, DirectGraphicsMode, WIDTH, HEIGHT, PAGES>
+where
+    DI: ReadableDataCommand,
+    SPECS: DisplaySpecs<WIDTH, HEIGHT, PAGES>,
+{
+    fn draw_pixel(
+        &mut self,
+        Point { x, y }: Point,
+        color: BinaryColor,
+    ) -> Result<(), DisplayError> {
+        if x < 0 || y < 0 || x as usize >= WIDTH || y as usize >= HEIGHT {
+            return Ok(());
+        }
+        let (page, column) = ((y / 8) as u8, x as u8);
+
+        if self.mode.cursor != Some((page, column)) {
+            self.end_read_modify_write()?;
+            self.interface.send_command_sequence(
+                SPECS::CONTROLLER,
+                &[
+                    Command::PageAddressSet { address: page },
+                    Command::ColumnAddressSet {
+                        address: self.column_offset() + column,
+                    },
+                    Command::ReadModifyWrite,
+                ],
+            )?;
+            self.mode.cursor = Some((page, column));
+        }
+
+        // The first read only latches the byte at the current address
+        let mut data = [0; 2];
+        self.interface.read_data(&mut data)?;
+
+        let mask = 1u8 << (y % 8);
+        let updated = match color {
+            BinaryColor::On => data[1] | mask,
+            BinaryColor::Off => data[1] & !mask,
+        };
+
+        if updated != data[1] {
+            self.interface.send_data(U8(&[updated]))?;
+            // Writing moves to the next column
+            self.mode.cursor = Some((page, column + 1));
+        }
+
+        Ok(())
+    }
+
+    fn end_read_modify_write(&mut self) -> Result<(), DisplayError> {
+        if self.mode.cursor.take().is_some() {
+            self.interface
+                .send_command(SPECS::CONTROLLER, Command::End)?;
+        }
+        Ok(())
+    }
+}
+
+impl<DI, SPECS, const WIDTH: usize, const HEIGHT: usize, const PAGES: usize> DrawTarget
+    for ST7565<DI, SPECS, DirectGraphicsMode, WIDTH, HEIGHT, PAGES>
+where
+    DI: ReadableDataCommand,
+    SPECS: DisplaySpecs<WIDTH, HEIGHT, PAGES>,
+{
+    type Color = BinaryColor;
+    type Error = DisplayError;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let result = pixels
+            .into_iter()
+            .try_for_each(|Pixel(point, color)| self.draw_pixel(point, color));
+
+        // Always leave the read-modify-write mode, even after an error
+        let end = self.end_read_modify_write();
+        result.and(end)
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.end_read_modify_write()?;
+
+        // Entire pages get overwritten, so nothing has to be read
+        let data = match color {
+            BinaryColor::On => [0xFF; WIDTH],
+            BinaryColor::Off => [0x00; WIDTH],
+        };
+        for page in 0..PAGES {
+            self.interface.send_command_sequence(
+                SPECS::CONTROLLER,
+                &[
+                    Command::PageAddressSet {
+                        address: page as u8,
+                    },
+                    Command::ColumnAddressSet {
+                        address: self.column_offset(),
+                    },
+                ],
+            )?;
+            self.interface.send_data(U8(&data))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<DI, SPECS, const WIDTH: usize, const HEIGHT: usize, const PAGES: usize> OriginDimensions
+    for ST7565<DI, SPECS, DirectGraphicsMode, WIDTH, HEIGHT, PAGES>
+where
+    SPECS: DisplaySpecs<WIDTH, HEIGHT, PAGES>,
+{
+    fn size(&self) -> Size {
+        Size::new(WIDTH as u32, HEIGHT as u32)
+    }
+}
@@ -1,26 +1,183 @@
 use display_interface::{DataFormat::U8, DisplayError, WriteOnlyDataCommand};
 use embedded_graphics_core::{
     draw_target::DrawTarget,
-    geometry::{OriginDimensions, Point, Size},
+    geometry::{Dimensions, OriginDimensions, Point, Size},
     pixelcolor::BinaryColor,
+    primitives::{PointsIter, Rectangle},
     Pixel,
 };
 
+use core::ops::Range;
+
+use super::page_buffer::{DirtySpans, PageTransfer};
 use crate::{
-    command::{Command, SendSt7565Command},
-    DisplaySpecs, GraphicsPageBuffer, ST7565,
+    command::SendSt7565Command,
+    types::{Orientation, Rotation},
+    DisplaySpecs, FlushError, GraphicsPageBuffer, ShadowBuffer, ST7565,
 };
 
 /// In this mode, the driver can be used as a [DrawTarget] for the [embedded_graphics](embedded_graphics_core) crate.
 pub struct GraphicsMode<'a, const WIDTH: usize, const PAGES: usize> {
-    page_buffers: &'a mut GraphicsPageBuffer<WIDTH, PAGES>,
+    pub(crate) page_buffers: &'a mut GraphicsPageBuffer<WIDTH, PAGES>,
+    pub(crate) rotation: Rotation,
+    pub(crate) double_buffer: Option<DoubleBuffer<PAGES>>,
+    pub(crate) shadow: Option<&'a mut ShadowBuffer<WIDTH, PAGES>>,
+}
+
+/// The state of the hardware double buffering.
+///
+/// The display RAM is split into two halves; one of them is displayed,
+/// while the next frame gets written into the other one.
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct DoubleBuffer<const PAGES: usize> {
+    /// Whether the second half of the display RAM is currently displayed
+    pub second_half_visible: bool,
+    /// The ranges that changed in the previous frame.
+    ///
+    /// The hidden half does not contain those changes yet.
+    pub previous_dirty: [DirtySpans; PAGES],
+}
+
+impl<const PAGES: usize> DoubleBuffer<PAGES> {
+    /// The first RAM page of the hidden half
+    pub fn hidden_first_page(&self) -> usize {
+        if self.second_half_visible {
+            0
+        } else {
+            PAGES
+        }
+    }
 }
 
 /// Initialize GraphicsMode with a page buffer.
 impl<'a, const WIDTH: usize, const PAGES: usize> GraphicsMode<'a, WIDTH, PAGES> {
     pub(crate) fn new(page_buffers: &'a mut GraphicsPageBuffer<WIDTH, PAGES>) -> Self {
         page_buffers.mark_dirty();
-        Self { page_buffers }
+        Self {
+            page_buffers,
+            rotation: Rotation::Rotate0,
+            double_buffer: None,
+            shadow: None,
+        }
+    }
+
+    pub(crate) fn new_double_buffered(
+        page_buffers: &'a mut GraphicsPageBuffer<WIDTH, PAGES>,
+    ) -> Self {
+        Self {
+            double_buffer: Some(DoubleBuffer {
+                second_half_visible: false,
+                previous_dirty: [DirtySpans::full(WIDTH); PAGES],
+            }),
+            ..Self::new(page_buffers)
+        }
+    }
+
+    /// Marks the entire buffer as dirty, in both halves of the display RAM
+    pub(crate) fn mark_dirty(&mut self) {
+        self.page_buffers.mark_dirty();
+        if let Some(double_buffer) = &mut self.double_buffer {
+            double_buffer.previous_dirty = [DirtySpans::full(WIDTH); PAGES];
+        }
+    }
+
+    /// The line offset that displays the hidden half of the display RAM,
+    /// if double buffering is enabled
+    pub(crate) fn hidden_line_offset(&self) -> Option<u8> {
+        let double_buffer = self.double_buffer.as_ref()?;
+        Some((double_buffer.hidden_first_page() * 8) as u8)
+    }
+
+    /// The transfers that write the frame into the hidden half of the display RAM.
+    ///
+    /// The hidden half does not contain the changes of the previous frame yet,
+    /// so they get sent as well.
+    pub(crate) fn double_buffered_transfers(
+        &self,
+        column_offset: u8,
+    ) -> impl Iterator<Item = PageTransfer<&[u8]>> + '_ {
+        self.double_buffer.iter().flat_map(move |double_buffer| {
+            let first_page = double_buffer.hidden_first_page();
+            self.page_buffers
+                .pages
+                .iter()
+                .enumerate()
+                .flat_map(move |(index, page)| {
+                    let dirty = page.dirty.union(&double_buffer.previous_dirty[index]);
+                    page.transfers(index, first_page + index, column_offset, dirty)
+                })
+        })
+    }
+
+    /// Finishes a double buffered flush with the result of displaying the hidden half.
+    ///
+    /// If it failed, nothing gets marked as clean.
+    pub(crate) fn finish_swap(
+        &mut self,
+        result: Result<(), DisplayError>,
+    ) -> Result<(), FlushError> {
+        result.map_err(|error| FlushError {
+            page: PAGES as u8,
+            error,
+        })?;
+
+        if let Some(double_buffer) = &mut self.double_buffer {
+            for (previous, page) in double_buffer
+                .previous_dirty
+                .iter_mut()
+                .zip(self.page_buffers.pages.iter_mut())
+            {
+                *previous = page.dirty;
+                page.mark_clean();
+            }
+            double_buffer.second_half_visible = !double_buffer.second_half_visible;
+        }
+        Ok(())
+    }
+
+    /// The transfers that send the changes of the given page.
+    ///
+    /// With a [`ShadowBuffer`], only the bytes that differ from the display content get sent.
+    pub(crate) fn page_transfers(
+        &self,
+        index: usize,
+        column_offset: u8,
+    ) -> impl Iterator<Item = PageTransfer<&[u8]>> + '_ {
+        let page = &self.page_buffers.pages[index];
+        let dirty = match &self.shadow {
+            Some(shadow) => shadow.changed(index, page),
+            None => page.dirty,
+        };
+        page.transfers(index, index, column_offset, dirty)
+    }
+
+    /// Finishes the flush of the given page with the result of its transfers.
+    ///
+    /// The page is only marked as clean if all of them succeeded.
+    pub(crate) fn finish_page(
+        &mut self,
+        index: usize,
+        result: Result<(), DisplayError>,
+    ) -> Result<(), FlushError> {
+        let page = &mut self.page_buffers.pages[index];
+        if let Err(error) = result {
+            // Some of the transfers may have reached the display already,
+            // so the shadow buffer no longer knows the display content
+            page.forced = true;
+            return Err(FlushError {
+                page: index as u8,
+                error,
+            });
+        }
+
+        if page.dirty.is_empty() {
+            return Ok(());
+        }
+        if let Some(shadow) = &mut self.shadow {
+            shadow.update(index, page);
+        }
+        page.mark_clean();
+        Ok(())
     }
 }
 
@@ -40,25 +197,71 @@ impl<
     /// Flushes the internal buffer to the screen.
     ///
     /// Needs to be called after drawing to actually display the data on screen.
-    pub fn flush(&mut self) -> Result<(), DisplayError> {
-        for (address, page) in self.mode.page_buffers.pages.iter_mut().enumerate() {
-            let address = address as u8;
-
-            if let Some((start, end)) = page.dirty.take() {
-                if start < end && start < WIDTH {
-                    self.interface
-                        .send_command(Command::PageAddressSet { address })?;
-                    self.interface.send_command(Command::ColumnAddressSet {
-                        address: SPECS::COLUMN_OFFSET + start as u8,
-                    })?;
-                    self.interface.send_data(U8(&page.data[start..end]))?;
+    ///
+    /// A page is only marked as clean after its data was transmitted successfully.
+    /// If the transmission fails, the failed page and all following pages stay dirty,
+    /// so a subsequent `flush()` will retry them. With a [`ShadowBuffer`], the failed
+    /// page gets sent without comparing it against the shadow buffer.
+    ///
+    /// While the display is in a power save state, flushing only updates the display RAM;
+    /// the display stays off until [`wake()`](ST7565::wake) is called.
+    ///
+    /// In double buffered mode, the frame gets written into the hidden half of the display RAM,
+    /// which then gets displayed at once. If this fails, nothing gets marked as clean
+    /// and the visible half stays untouched. A failure of the final swap is reported
+    /// with `PAGES` as the page number.
+    pub fn flush(&mut self) -> Result<(), FlushError> {
+        let column_offset = self.column_offset();
+
+        if let Some(line_offset) = self.mode.hidden_line_offset() {
+            for transfer in self.mode.double_buffered_transfers(column_offset) {
+                self.interface
+                    .send_page_data(
+                        SPECS::CONTROLLER,
+                        transfer.address,
+                        transfer.column,
+                        U8(transfer.data),
+                    )
+                    .map_err(|error| transfer.error(error))?;
+            }
+
+            let result = self.set_line_offset(line_offset);
+            return self.mode.finish_swap(result);
+        }
+
+        for index in 0..PAGES {
+            let mut result = Ok(());
+            for transfer in self.mode.page_transfers(index, column_offset) {
+                result = self.interface.send_page_data(
+                    SPECS::CONTROLLER,
+                    transfer.address,
+                    transfer.column,
+                    U8(transfer.data),
+                );
+                if result.is_err() {
+                    break;
                 }
             }
+            self.mode.finish_page(index, result)?;
         }
 
         Ok(())
     }
 
+    /// Rotates the display by 180 degrees in hardware.
+    ///
+    /// Reverses the column and row scan directions and moves the content to the
+    /// columns and lines that are visible in the new orientation.
+    /// The whole buffer gets marked as dirty, so the next [`flush()`](ST7565::flush)
+    /// rewrites the display content at its new position.
+    ///
+    /// The orientation is kept across [`reset()`](ST7565::reset) calls.
+    pub fn set_orientation(&mut self, orientation: Orientation) -> Result<(), DisplayError> {
+        self.apply_change(self.orientation_change(orientation))?;
+        self.mode.mark_dirty();
+        Ok(())
+    }
+
     /// Release the display interface object
     ///
     /// This is meant for situations where the display interface is shared between several devices.
@@ -80,6 +283,7 @@ impl<
                 interface: (),
                 display_specs: self.display_specs,
                 mode: self.mode,
+                settings: self.settings,
             },
             self.interface,
         )
@@ -106,7 +310,106 @@ impl<'a, SPECS, const WIDTH: usize, const HEIGHT: usize, const PAGES: usize>
             interface,
             display_specs: self.display_specs,
             mode: self.mode,
+            settings: self.settings,
+        }
+    }
+}
+
+/// ---- Rotation of the graphics mode ----
+/// =======================================
+///
+/// Available with and without an attached display interface.
+impl<'a, DI, SPECS, const WIDTH: usize, const HEIGHT: usize, const PAGES: usize>
+    ST7565<DI, SPECS, GraphicsMode<'a, WIDTH, PAGES>, WIDTH, HEIGHT, PAGES>
+where
+    SPECS: DisplaySpecs<WIDTH, HEIGHT, PAGES>,
+{
+    /// Sets the rotation of everything drawn from now on.
+    ///
+    /// For 90 and 270 degrees, width and height of the [DrawTarget] are swapped.
+    ///
+    /// Content that is already in the buffer is not rotated.
+    pub fn set_rotation(&mut self, rotation: Rotation) {
+        self.mode.rotation = rotation;
+    }
+
+    /// The current rotation
+    pub fn rotation(&self) -> Rotation {
+        self.mode.rotation
+    }
+
+    /// Maps a rotated point to the column and row of the display.
+    ///
+    /// Returns `None` if the point is outside of the display.
+    fn physical_position(&self, Point { x, y }: Point) -> Option<(usize, usize)> {
+        let size = self.size();
+        if x < 0 || y < 0 || x as u32 >= size.width || y as u32 >= size.height {
+            return None;
         }
+
+        let (x, y) = (x as usize, y as usize);
+        Some(match self.mode.rotation {
+            Rotation::Rotate0 => (x, y),
+            Rotation::Rotate90 => (WIDTH - 1 - y, x),
+            Rotation::Rotate180 => (WIDTH - 1 - x, HEIGHT - 1 - y),
+            Rotation::Rotate270 => (y, HEIGHT - 1 - x),
+        })
+    }
+
+    /// Maps a rotated area to the columns and rows of the display.
+    ///
+    /// Returns `None` if the area does not overlap with the display.
+    fn physical_area(&self, area: &Rectangle) -> Option<(Range<usize>, Range<usize>)> {
+        let area = area.intersection(&self.bounding_box());
+        let (x0, y0) = self.physical_position(area.top_left)?;
+        let (x1, y1) = self.physical_position(area.bottom_right()?)?;
+        Some((x0.min(x1)..x0.max(x1) + 1, y0.min(y1)..y0.max(y1) + 1))
+    }
+}
+
+/// Sets or clears the bits of `mask` in a byte of the page buffer.
+///
+/// Returns whether the byte changed.
+fn apply_mask(byte: &mut u8, mask: u8, color: BinaryColor) -> bool {
+    let updated = match color {
+        BinaryColor::On => *byte | mask,
+        BinaryColor::Off => *byte & !mask,
+    };
+    let changed = updated != *byte;
+    *byte = updated;
+    changed
+}
+
+/// Extends a range of changed columns by the given column
+fn include_column(changed: &mut Option<(usize, usize)>, column: usize) {
+    *changed = Some(match *changed {
+        Some((start, end)) => (start.min(column), end.max(column + 1)),
+        None => (column, column + 1),
+    });
+}
+
+/// ---- Shadow buffer of the graphics mode ----
+/// ============================================
+///
+/// Available with and without an attached display interface.
+impl<'a, DI, SPECS, const WIDTH: usize, const HEIGHT: usize, const PAGES: usize>
+    ST7565<DI, SPECS, GraphicsMode<'a, WIDTH, PAGES>, WIDTH, HEIGHT, PAGES>
+where
+    SPECS: DisplaySpecs<WIDTH, HEIGHT, PAGES>,
+{
+    /// Attaches a [`ShadowBuffer`] that holds a copy of the data last sent to the display.
+    ///
+    /// [`flush()`](ST7565::flush) then compares the dirty parts of the page buffer against it
+    /// and only sends the bytes that actually changed, so pixels that were toggled
+    /// back to their old value cause no traffic.
+    ///
+    /// The next flush sends the entire buffer, to get the shadow buffer in sync with the display.
+    ///
+    /// Has no effect in double buffered mode.
+    pub fn with_shadow_buffer(mut self, shadow: &'a mut ShadowBuffer<WIDTH, PAGES>) -> Self {
+        self.mode.mark_dirty();
+        self.mode.shadow = Some(shadow);
+        self
     }
 }
 
@@ -122,31 +425,19 @@ where
     where
         I: IntoIterator<Item = Pixel<Self::Color>>,
     {
-        for Pixel(Point { x, y }, color) in pixels.into_iter() {
-            if x < 0 || y < 0 || x as usize >= WIDTH || y as usize >= HEIGHT {
-                continue;
-            }
+        for Pixel(point, color) in pixels.into_iter() {
+            let (x, y) = match self.physical_position(point) {
+                Some(position) => position,
+                None => continue,
+            };
 
-            let x = x as usize;
-            let page = (y / 8) as usize;
+            let page = y / 8;
             let y_offset = (y % 8) as u8;
 
             if let Some(page) = self.mode.page_buffers.pages.get_mut(page) {
                 if let Some(buffer_line) = page.data.get_mut(x) {
-                    let updated = match color {
-                        BinaryColor::On => *buffer_line | (1u8 << y_offset),
-                        BinaryColor::Off => *buffer_line & (!(1u8 << y_offset)),
-                    };
-
-                    if updated != *buffer_line {
-                        match &mut page.dirty {
-                            Some(dirty_range) => {
-                                dirty_range.0 = dirty_range.0.min(x);
-                                dirty_range.1 = dirty_range.1.max(x + 1);
-                            }
-                            None => page.dirty = Some((x, x + 1)),
-                        };
-                        *buffer_line = updated;
+                    if apply_mask(buffer_line, 1u8 << y_offset, color) {
+                        page.dirty.add(x, x + 1);
                     }
                 }
             }
@@ -154,6 +445,69 @@ where
 
         Ok(())
     }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        // Collect the changed columns first, to update the dirty spans only once per page
+        let mut changed = [None; PAGES];
+
+        for (point, color) in area.points().zip(colors) {
+            let (x, y) = match self.physical_position(point) {
+                Some(position) => position,
+                None => continue,
+            };
+
+            if let Some(page) = self.mode.page_buffers.pages.get_mut(y / 8) {
+                if apply_mask(&mut page.data[x], 1u8 << (y % 8), color) {
+                    include_column(&mut changed[y / 8], x);
+                }
+            }
+        }
+
+        for (page, changed) in self.mode.page_buffers.pages.iter_mut().zip(changed.iter()) {
+            if let Some((start, end)) = *changed {
+                page.dirty.add(start, end);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let (columns, rows) = match self.physical_area(area) {
+            Some(area) => area,
+            None => return Ok(()),
+        };
+
+        for (index, page) in self.mode.page_buffers.pages.iter_mut().enumerate() {
+            let top = rows.start.max(index * 8);
+            let bottom = rows.end.min(index * 8 + 8);
+            if top >= bottom {
+                continue;
+            }
+
+            // Partial pages at the top and bottom of the area only get some of their bits modified
+            let mask = (0xFFu8 >> (8 - (bottom - top))) << (top % 8);
+
+            let mut changed = None;
+            for (column, byte) in (columns.start..).zip(&mut page.data[columns.clone()]) {
+                if apply_mask(byte, mask, color) {
+                    include_column(&mut changed, column);
+                }
+            }
+            if let Some((start, end)) = changed {
+                page.dirty.add(start, end);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.fill_solid(&self.bounding_box(), color)
+    }
 }
 
 impl<'a, DI, SPECS, const WIDTH: usize, const HEIGHT: usize, const PAGES: usize> OriginDimensions
@@ -162,9 +516,17 @@ where
     SPECS: DisplaySpecs<WIDTH, HEIGHT, PAGES>,
 {
     fn size(&self) -> Size {
-        Size {
+        let size = Size {
             width: WIDTH as u32,
             height: HEIGHT as u32,
+        };
+
+        match self.mode.rotation {
+            Rotation::Rotate0 | Rotation::Rotate180 => size,
+            Rotation::Rotate90 | Rotation::Rotate270 => Size {
+                width: size.height,
+                height: size.width,
+            },
         }
     }
 }
@@ -0,0 +1,189 @@
+use display_interface::{DataFormat::U8Iter, DisplayError, WriteOnlyDataCommand};
+use embedded_graphics_core::{
+    draw_target::DrawTarget,
+    geometry::{OriginDimensions, Size},
+    pixelcolor::{Gray2, GrayColor},
+    Pixel,
+};
+
+use super::page_buffer::{DirtySpans, PageTransfer};
+use crate::{
+    command::{Command, SendSt7565Command},
+    DisplaySpecs, FlushError, GrayscalePageBuffer, ST7565,
+};
+
+/// In this mode, the driver can be used as a grayscale [DrawTarget] for the [embedded_graphics](embedded_graphics_core) crate.
+///
+/// Only supported by the ST7571.
+pub struct GrayscaleMode<'a, const WIDTH: usize, const PAGES: usize> {
+    pub(crate) page_buffers: &'a mut GrayscalePageBuffer<WIDTH, PAGES>,
+}
+
+/// Initialize GrayscaleMode with a page buffer.
+impl<'a, const WIDTH: usize, const PAGES: usize> GrayscaleMode<'a, WIDTH, PAGES> {
+    pub(crate) fn new(page_buffers: &'a mut GrayscalePageBuffer<WIDTH, PAGES>) -> Self {
+        page_buffers.mark_dirty();
+        Self { page_buffers }
+    }
+
+    /// The transfers that send the changes of the given page
+    pub(crate) fn page_transfers(
+        &self,
+        index: usize,
+        column_offset: u8,
+    ) -> impl Iterator<Item = PageTransfer<impl Iterator<Item = u8> + '_>> + '_ {
+        self.page_buffers.pages[index].transfers(index, column_offset)
+    }
+
+    /// Marks the given page as synchronized with the display,
+    /// after all of its transfers succeeded
+    pub(crate) fn finish_page(&mut self, index: usize) {
+        self.page_buffers.pages[index].dirty = DirtySpans::new();
+    }
+}
+
+/// The gray level of a color, from `0` (white) to `3` (black)
+fn gray_level(color: Gray2) -> u8 {
+    3 - color.luma()
+}
+
+/// The two commands that set the PWM of a gray level in every frame
+pub(crate) fn gray_level_commands(color: Gray2, pwm: u8) -> [Command; 2] {
+    let register = gray_level(color) * 2;
+    let value = (pwm & 0x0F) * 0x11;
+    [
+        Command::GrayLevelSet { register, value },
+        Command::GrayLevelSet {
+            register: register + 1,
+            value,
+        },
+    ]
+}
+
+/// ---- Functionality of the grayscale mode ----
+/// =============================================
+///
+/// In this mode, the driver can be used as a grayscale [DrawTarget] for the [embedded_graphics](embedded_graphics_core) crate.
+impl<
+        'a,
+        DI: WriteOnlyDataCommand,
+        SPECS: DisplaySpecs<WIDTH, HEIGHT, PAGES>,
+        const WIDTH: usize,
+        const HEIGHT: usize,
+        const PAGES: usize,
+    > ST7565<DI, SPECS, GrayscaleMode<'a, WIDTH, PAGES>, WIDTH, HEIGHT, PAGES>
+{
+    /// Flushes the internal buffer to the screen.
+    ///
+    /// Needs to be called after drawing to actually display the data on screen.
+    ///
+    /// Every column is sent as two bytes, the low bit plane followed by the high bit plane.
+    ///
+    /// A page is only marked as clean after its data was transmitted successfully.
+    /// If the transmission fails, the failed page and all following pages stay dirty,
+    /// so a subsequent `flush()` will retry them.
+    pub fn flush(&mut self) -> Result<(), FlushError> {
+        let column_offset = self.column_offset();
+
+        for index in 0..PAGES {
+            for mut transfer in self.mode.page_transfers(index, column_offset) {
+                self.interface
+                    .send_page_data(
+                        SPECS::CONTROLLER,
+                        transfer.address,
+                        transfer.column,
+                        U8Iter(&mut transfer.data),
+                    )
+                    .map_err(|error| transfer.error(error))?;
+            }
+            self.mode.finish_page(index);
+        }
+
+        Ok(())
+    }
+
+    /// Sets the brightness of a gray level.
+    ///
+    /// `pwm` is the number of PWM steps the pixels of this level are dark,
+    /// from `0` (white) to `15` (black). It is used for all frames of the
+    /// frame rate control.
+    ///
+    /// The levels are reset to their defaults by [`reset()`](ST7565::reset).
+    pub fn set_gray_level(&mut self, color: Gray2, pwm: u8) -> Result<(), DisplayError> {
+        self.interface
+            .send_command_sequence(SPECS::CONTROLLER, &gray_level_commands(color, pwm))
+    }
+}
+
+impl<'a, DI, SPECS, const WIDTH: usize, const HEIGHT: usize, const PAGES: usize> DrawTarget
+    for ST7565<DI, SPECS, GrayscaleMode<'a, WIDTH, PAGES>, WIDTH, HEIGHT, PAGES>
+where
+    SPECS: DisplaySpecs<WIDTH, HEIGHT, PAGES>,
+{
+    type Color = Gray2;
+    type Error = core::convert::Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(point, color) in pixels.into_iter() {
+            if point.x < 0 || point.y < 0 {
+                continue;
+            }
+            let (x, y) = (point.x as usize, point.y as usize);
+            if x >= WIDTH || y >= HEIGHT {
+                continue;
+            }
+
+            let level = gray_level(color);
+            let mask = 1u8 << (y % 8);
+
+            if let Some(page) = self.mode.page_buffers.pages.get_mut(y / 8) {
+                let mut changed = false;
+                for (bit, plane) in page.planes.iter_mut().enumerate() {
+                    let byte = &mut plane[x];
+                    let updated = if level & (1 << bit) != 0 {
+                        *byte | mask
+                    } else {
+                        *byte & !mask
+                    };
+                    changed |= updated != *byte;
+                    *byte = updated;
+                }
+                if changed {
+                    page.dirty.add(x, x + 1);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        let level = gray_level(color);
+
+        for page in &mut self.mode.page_buffers.pages {
+            for (bit, plane) in page.planes.iter_mut().enumerate() {
+                let fill = if level & (1 << bit) != 0 { 0xFF } else { 0x00 };
+                *plane = [fill; WIDTH];
+            }
+            page.dirty = DirtySpans::full(WIDTH);
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a, DI, SPECS, const WIDTH: usize, const HEIGHT: usize, const PAGES: usize> OriginDimensions
+    for ST7565<DI, SPECS, GrayscaleMode<'a, WIDTH, PAGES>, WIDTH, HEIGHT, PAGES>
+where
+    SPECS: DisplaySpecs<WIDTH, HEIGHT, PAGES>,
+{
+    fn size(&self) -> Size {
+        Size {
+            width: WIDTH as u32,
+            height: HEIGHT as u32,
+        }
+    }
+}
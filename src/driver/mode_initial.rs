@@ -2,8 +2,15 @@ use core::marker::PhantomData;
 
 use display_interface::WriteOnlyDataCommand;
 
-use super::{mode_graphics::GraphicsMode, mode_raw::RawMode};
-use crate::{DisplaySpecs, GraphicsPageBuffer, ST7565};
+use super::{
+    common_functionality::ram_pages, mode_direct_graphics::DirectGraphicsMode,
+    mode_graphics::GraphicsMode, mode_grayscale::GrayscaleMode, mode_page_band::PageBandMode,
+    mode_raw::RawMode, mode_terminal::TerminalMode, DisplaySettings,
+};
+use crate::{
+    types::Controller, DisplaySpecs, GraphicsPageBuffer, GrayscalePageBuffer, PageBandBuffer,
+    ST7565,
+};
 
 /// This mode is purely to transition into other modes.
 pub struct InitialMode;
@@ -23,14 +30,32 @@ where
             interface,
             display_specs: PhantomData,
             mode: InitialMode,
+            settings: DisplaySettings::from_specs::<SPECS, WIDTH, HEIGHT, PAGES>(),
         }
     }
+}
+
+impl<DI, SPECS, const WIDTH: usize, const HEIGHT: usize, const PAGES: usize>
+    ST7565<DI, SPECS, InitialMode, WIDTH, HEIGHT, PAGES>
+where
+    SPECS: DisplaySpecs<WIDTH, HEIGHT, PAGES>,
+{
+    const DOUBLE_BUFFER_FITS: () = assert!(
+        PAGES * 2 <= ram_pages(SPECS::CONTROLLER) as usize,
+        "The display RAM is too small to hold two frames of this display"
+    );
+
+    const SUPPORTS_GRAYSCALE: () = assert!(
+        matches!(SPECS::CONTROLLER, Controller::St7571),
+        "Grayscale mode is only supported by the ST7571"
+    );
 
     fn into_mode<MODE>(self, mode: MODE) -> ST7565<DI, SPECS, MODE, WIDTH, HEIGHT, PAGES> {
         ST7565 {
             interface: self.interface,
             display_specs: self.display_specs,
             mode,
+            settings: self.settings,
         }
     }
 
@@ -51,4 +76,99 @@ where
     ) -> ST7565<DI, SPECS, GraphicsMode<'_, WIDTH, PAGES>, WIDTH, HEIGHT, PAGES> {
         self.into_mode(GraphicsMode::new(buffer))
     }
+
+    /// Transitions the driver into double buffered graphics mode.
+    ///
+    /// Same as [`into_graphics_mode()`](Self::into_graphics_mode), but every
+    /// [`flush()`](ST7565::flush) writes into the hidden half of the display RAM and then
+    /// displays it at once, which prevents tearing.
+    ///
+    /// Only available for displays that are at most half as tall as the display RAM;
+    /// taller displays are rejected at compile time:
+    ///
+    /// ```compile_fail
+    /// # use display_interface::{DataFormat, DisplayError, WriteOnlyDataCommand};
+    /// # use st7565::{displays::DOGL128_6_EXT12V, GraphicsPageBuffer, ST7565};
+    /// # struct Interface;
+    /// # impl WriteOnlyDataCommand for Interface {
+    /// #     fn send_commands(&mut self, _: DataFormat<'_>) -> Result<(), DisplayError> { Ok(()) }
+    /// #     fn send_data(&mut self, _: DataFormat<'_>) -> Result<(), DisplayError> { Ok(()) }
+    /// # }
+    /// let mut buffer = GraphicsPageBuffer::new();
+    /// let disp = ST7565::new(Interface, DOGL128_6_EXT12V)
+    ///     .into_double_buffered_graphics_mode(&mut buffer);
+    /// ```
+    pub fn into_double_buffered_graphics_mode(
+        self,
+        buffer: &mut GraphicsPageBuffer<WIDTH, PAGES>,
+    ) -> ST7565<DI, SPECS, GraphicsMode<'_, WIDTH, PAGES>, WIDTH, HEIGHT, PAGES> {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::DOUBLE_BUFFER_FITS;
+
+        self.into_mode(GraphicsMode::new_double_buffered(buffer))
+    }
+
+    /// Transitions the driver into grayscale mode.
+    ///
+    /// Grayscale mode enables the driver to be used as a [DrawTarget](embedded_graphics_core::draw_target::DrawTarget)
+    /// with four gray levels. The grayscale mode of the controller gets selected
+    /// by [`reset()`](ST7565::reset), which has to be called afterwards.
+    ///
+    /// Only available for the ST7571; other controllers are rejected at compile time:
+    ///
+    /// ```compile_fail
+    /// # use display_interface::{DataFormat, DisplayError, WriteOnlyDataCommand};
+    /// # use st7565::{displays::DOGM132W5, GrayscalePageBuffer, ST7565};
+    /// # struct Interface;
+    /// # impl WriteOnlyDataCommand for Interface {
+    /// #     fn send_commands(&mut self, _: DataFormat<'_>) -> Result<(), DisplayError> { Ok(()) }
+    /// #     fn send_data(&mut self, _: DataFormat<'_>) -> Result<(), DisplayError> { Ok(()) }
+    /// # }
+    /// let mut buffer = GrayscalePageBuffer::new();
+    /// let disp = ST7565::new(Interface, DOGM132W5).into_grayscale_mode(&mut buffer);
+    /// ```
+    pub fn into_grayscale_mode(
+        mut self,
+        buffer: &mut GrayscalePageBuffer<WIDTH, PAGES>,
+    ) -> ST7565<DI, SPECS, GrayscaleMode<'_, WIDTH, PAGES>, WIDTH, HEIGHT, PAGES> {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::SUPPORTS_GRAYSCALE;
+
+        self.settings.grayscale = true;
+        self.into_mode(GrayscaleMode::new(buffer))
+    }
+
+    /// Transitions the driver into direct graphics mode.
+    ///
+    /// Like graphics mode, direct graphics mode enables the driver to be used as a
+    /// [DrawTarget](embedded_graphics_core::draw_target::DrawTarget), but without a buffer.
+    /// Pixels get modified directly in the display RAM, which requires a
+    /// [`ReadableDataCommand`](crate::interface::ReadableDataCommand) display interface.
+    pub fn into_direct_graphics_mode(
+        self,
+    ) -> ST7565<DI, SPECS, DirectGraphicsMode, WIDTH, HEIGHT, PAGES> {
+        self.into_mode(DirectGraphicsMode::new())
+    }
+
+    /// Transitions the driver into page band mode.
+    ///
+    /// Page band mode renders every frame in bands of `BAND_PAGES` pages through a
+    /// draw closure, which only requires a buffer for a single band.
+    /// See [`render()`](ST7565::render).
+    ///
+    /// A band that is at least one page tall is enforced at compile time.
+    pub fn into_page_band_mode<const BAND_PAGES: usize>(
+        self,
+        buffer: &mut PageBandBuffer<WIDTH, BAND_PAGES>,
+    ) -> ST7565<DI, SPECS, PageBandMode<'_, WIDTH, BAND_PAGES>, WIDTH, HEIGHT, PAGES> {
+        self.into_mode(PageBandMode::new(buffer))
+    }
+
+    /// Transitions the driver into terminal mode.
+    ///
+    /// Terminal mode turns the display into a text console that implements [`core::fmt::Write`].
+    /// Call [`clear()`](ST7565::clear) before printing the first text.
+    pub fn into_terminal_mode(self) -> ST7565<DI, SPECS, TerminalMode, WIDTH, HEIGHT, PAGES> {
+        self.into_mode(TerminalMode::new(ram_pages(SPECS::CONTROLLER)))
+    }
 }
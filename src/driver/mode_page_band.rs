@@ -0,0 +1,186 @@
+use core::ops::Range;
+
+use display_interface::{DataFormat::U8, WriteOnlyDataCommand};
+use embedded_graphics_core::{
+    draw_target::DrawTarget,
+    geometry::{OriginDimensions, Point, Size},
+    pixelcolor::BinaryColor,
+    primitives::Rectangle,
+    Pixel,
+};
+
+use super::page_buffer::{PageBandBuffer, PageTransfer};
+use crate::{command::SendSt7565Command, DisplaySpecs, FlushError, ST7565};
+
+/// In this mode, the display content gets rendered in bands of a few pages at a time.
+///
+/// Instead of a buffer for the entire display, only a [`PageBandBuffer`] that is
+/// `BAND_PAGES` pages tall is required. For every frame, the draw closure passed to
+/// [`render()`](ST7565::render) gets called once per band.
+pub struct PageBandMode<'a, const WIDTH: usize, const BAND_PAGES: usize> {
+    pub(crate) buffer: &'a mut PageBandBuffer<WIDTH, BAND_PAGES>,
+}
+
+impl<'a, const WIDTH: usize, const BAND_PAGES: usize> PageBandMode<'a, WIDTH, BAND_PAGES> {
+    const BAND_NOT_EMPTY: () = assert!(
+        BAND_PAGES > 0,
+        "A page band has to be at least one page tall"
+    );
+
+    pub(crate) fn new(buffer: &'a mut PageBandBuffer<WIDTH, BAND_PAGES>) -> Self {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::BAND_NOT_EMPTY;
+
+        Self { buffer }
+    }
+
+    /// Clears the buffer and returns the draw target for the band starting at the given page
+    pub(crate) fn band<const HEIGHT: usize>(
+        &mut self,
+        first_page: usize,
+    ) -> PageBand<'_, WIDTH, HEIGHT, BAND_PAGES> {
+        self.buffer.pages = [[0; WIDTH]; BAND_PAGES];
+        PageBand {
+            pages: &mut self.buffer.pages,
+            first_page,
+        }
+    }
+
+    /// The transfers that send the band starting at the given page,
+    /// without the pages below the display
+    pub(crate) fn band_transfers<const PAGES: usize>(
+        &self,
+        first_page: usize,
+        column_offset: u8,
+    ) -> impl Iterator<Item = PageTransfer<&[u8]>> + '_ {
+        let band_pages = BAND_PAGES.min(PAGES - first_page);
+        let pages = (first_page..).zip(&self.buffer.pages[..band_pages]);
+
+        pages.map(move |(page, data)| PageTransfer {
+            page,
+            address: Some(page as u8),
+            column: column_offset,
+            data: &data[..],
+        })
+    }
+}
+
+/// A band of pages of the display, used as a [DrawTarget] in page band mode.
+///
+/// Covers the coordinates of the entire display; everything drawn outside of
+/// the band gets discarded.
+pub struct PageBand<'b, const WIDTH: usize, const HEIGHT: usize, const BAND_PAGES: usize> {
+    pages: &'b mut [[u8; WIDTH]; BAND_PAGES],
+    first_page: usize,
+}
+
+impl<'b, const WIDTH: usize, const HEIGHT: usize, const BAND_PAGES: usize>
+    PageBand<'b, WIDTH, HEIGHT, BAND_PAGES>
+{
+    /// The rows covered by this band
+    fn rows(&self) -> Range<usize> {
+        let top = self.first_page * 8;
+        top..(top + BAND_PAGES * 8).min(HEIGHT)
+    }
+
+    /// The area of the display covered by this band.
+    ///
+    /// Can be used to skip drawing objects that do not intersect with the band.
+    pub fn area(&self) -> Rectangle {
+        let rows = self.rows();
+        Rectangle::new(
+            Point::new(0, rows.start as i32),
+            Size::new(WIDTH as u32, rows.len() as u32),
+        )
+    }
+}
+
+impl<'b, const WIDTH: usize, const HEIGHT: usize, const BAND_PAGES: usize> DrawTarget
+    for PageBand<'b, WIDTH, HEIGHT, BAND_PAGES>
+{
+    type Color = BinaryColor;
+    type Error = core::convert::Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let rows = self.rows();
+
+        for Pixel(Point { x, y }, color) in pixels.into_iter() {
+            if x < 0 || y < 0 {
+                continue;
+            }
+            let (x, y) = (x as usize, y as usize);
+            if x >= WIDTH || !rows.contains(&y) {
+                continue;
+            }
+
+            let buffer_line = &mut self.pages[y / 8 - self.first_page][x];
+            let mask = 1u8 << (y % 8);
+            match color {
+                BinaryColor::On => *buffer_line |= mask,
+                BinaryColor::Off => *buffer_line &= !mask,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<'b, const WIDTH: usize, const HEIGHT: usize, const BAND_PAGES: usize> OriginDimensions
+    for PageBand<'b, WIDTH, HEIGHT, BAND_PAGES>
+{
+    fn size(&self) -> Size {
+        Size::new(WIDTH as u32, HEIGHT as u32)
+    }
+}
+
+/// ---- Functionality of the page band mode ----
+/// =============================================
+///
+/// In this mode, every frame gets rendered band by band through a draw closure.
+impl<
+        'a,
+        DI,
+        SPECS,
+        const WIDTH: usize,
+        const HEIGHT: usize,
+        const PAGES: usize,
+        const BAND_PAGES: usize,
+    > ST7565<DI, SPECS, PageBandMode<'a, WIDTH, BAND_PAGES>, WIDTH, HEIGHT, PAGES>
+where
+    DI: WriteOnlyDataCommand,
+    SPECS: DisplaySpecs<WIDTH, HEIGHT, PAGES>,
+{
+    /// Renders a frame and sends it to the display.
+    ///
+    /// `draw` gets called once for every band of the display, from top to bottom,
+    /// and has to draw the entire frame every time. Everything outside of the current
+    /// band is discarded; [`PageBand::area()`] can be used to skip objects that are not visible.
+    ///
+    /// Every band is sent to the display right after it was drawn.
+    pub fn render<F>(&mut self, mut draw: F) -> Result<(), FlushError>
+    where
+        F: FnMut(&mut PageBand<'_, WIDTH, HEIGHT, BAND_PAGES>),
+    {
+        let column_offset = self.column_offset();
+
+        for first_page in (0..PAGES).step_by(BAND_PAGES) {
+            draw(&mut self.mode.band(first_page));
+
+            for transfer in self.mode.band_transfers::<PAGES>(first_page, column_offset) {
+                self.interface
+                    .send_page_data(
+                        SPECS::CONTROLLER,
+                        transfer.address,
+                        transfer.column,
+                        U8(transfer.data),
+                    )
+                    .map_err(|error| transfer.error(error))?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -1,7 +1,7 @@
 use display_interface::{DataFormat::U8, DisplayError, WriteOnlyDataCommand};
 
 use crate::command::{Command, SendSt7565Command};
-use crate::ST7565;
+use crate::{DisplaySpecs, ST7565};
 
 /// Raw mode provides functionality to control the ST7565 chip directly
 /// through low-level commands.
@@ -15,17 +15,18 @@ impl<DI, SPECS, const WIDTH: usize, const HEIGHT: usize, const PAGES: usize>
     ST7565<DI, SPECS, RawMode, WIDTH, HEIGHT, PAGES>
 where
     DI: WriteOnlyDataCommand,
+    SPECS: DisplaySpecs<WIDTH, HEIGHT, PAGES>,
 {
     /// Sets the page to write into
     pub fn set_page(&mut self, page: u8) -> Result<(), DisplayError> {
         self.interface
-            .send_command(Command::PageAddressSet { address: page })
+            .send_command(SPECS::CONTROLLER, Command::PageAddressSet { address: page })
     }
 
     /// Sets the column to write into
     pub fn set_column(&mut self, address: u8) -> Result<(), DisplayError> {
         self.interface
-            .send_command(Command::ColumnAddressSet { address })
+            .send_command(SPECS::CONTROLLER, Command::ColumnAddressSet { address })
     }
 
     /// Writes raw pixel data.
@@ -40,14 +41,17 @@ where
     ///
     /// Effectively flips the display horizontally.
     pub fn adc_select(&mut self, reverse: bool) -> Result<(), DisplayError> {
-        self.interface.send_command(Command::AdcSelect { reverse })
+        self.interface
+            .send_command(SPECS::CONTROLLER, Command::AdcSelect { reverse })
     }
 
     /// Sets the common mode direction.
     ///
     /// Effectively flips the display vertically.
     pub fn common_output_mode_select(&mut self, reverse: bool) -> Result<(), DisplayError> {
-        self.interface
-            .send_command(Command::CommonOutputModeSelect { reverse })
+        self.interface.send_command(
+            SPECS::CONTROLLER,
+            Command::CommonOutputModeSelect { reverse },
+        )
     }
 }
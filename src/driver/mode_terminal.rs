@@ -0,0 +1,196 @@
+use display_interface::{DataFormat::U8, DisplayError, WriteOnlyDataCommand};
+
+use super::{
+    common_functionality::ram_pages,
+    font::{glyph, CHAR_WIDTH},
+};
+use crate::{
+    command::{Command, SendSt7565Command},
+    DisplaySpecs, ST7565,
+};
+
+/// In this mode, the driver acts as a text console.
+///
+/// Text gets rendered with a built-in monospaced font, one row per page.
+/// Once the cursor reaches the bottom of the display, the display scrolls
+/// through its RAM, so only the newly exposed row has to be redrawn.
+pub struct TerminalMode {
+    /// The cursor column, in characters
+    pub(crate) column: usize,
+    /// The cursor row, counted from the top of the display
+    pub(crate) row: usize,
+    /// The RAM page that is displayed at the top of the display
+    pub(crate) top_page: u8,
+    /// Whether the address of the display RAM matches the cursor
+    pub(crate) address_valid: bool,
+    /// The number of RAM pages the display scrolls through
+    pub(crate) ram_pages: u8,
+}
+
+impl TerminalMode {
+    pub(crate) fn new(ram_pages: u8) -> Self {
+        Self {
+            column: 0,
+            row: 0,
+            top_page: 0,
+            address_valid: false,
+            ram_pages,
+        }
+    }
+
+    /// The RAM page of the cursor row
+    pub(crate) fn page(&self) -> u8 {
+        (self.top_page + self.row as u8) % self.ram_pages
+    }
+
+    /// Moves the cursor to the start of the next row.
+    ///
+    /// If the display has to scroll, returns the RAM page that has to be cleared
+    /// and the new line offset.
+    pub(crate) fn newline(&mut self, rows: usize) -> Option<(u8, u8)> {
+        self.column = 0;
+        self.address_valid = false;
+
+        if self.row + 1 < rows {
+            self.row += 1;
+            None
+        } else {
+            self.top_page = (self.top_page + 1) % self.ram_pages;
+            Some((self.page(), self.top_page * 8))
+        }
+    }
+
+    pub(crate) fn carriage_return(&mut self) {
+        self.column = 0;
+        self.address_valid = false;
+    }
+
+    /// Moves the cursor behind the character that was just printed.
+    ///
+    /// The column address of the display RAM increments automatically
+    /// while writing data, so it still matches the cursor.
+    pub(crate) fn advance(&mut self) {
+        self.address_valid = true;
+        self.column += 1;
+    }
+}
+
+/// ---- Layout of the terminal mode ----
+/// =====================================
+impl<DI, SPECS, const WIDTH: usize, const HEIGHT: usize, const PAGES: usize>
+    ST7565<DI, SPECS, TerminalMode, WIDTH, HEIGHT, PAGES>
+where
+    SPECS: DisplaySpecs<WIDTH, HEIGHT, PAGES>,
+{
+    /// The number of characters per row
+    pub const COLUMNS: usize = WIDTH / CHAR_WIDTH;
+
+    /// The number of rows
+    pub const ROWS: usize = HEIGHT / 8;
+
+    /// The position of the cursor, as column and row
+    pub fn cursor(&self) -> (usize, usize) {
+        (self.mode.column, self.mode.row)
+    }
+
+    /// The commands that move the display RAM address to the cursor, if it does not match.
+    ///
+    /// The address is unknown until the character got printed and
+    /// [`TerminalMode::advance()`] was called.
+    pub(super) fn cursor_address(&mut self) -> Option<[Command; 2]> {
+        let set_address = !self.mode.address_valid;
+        self.mode.address_valid = false;
+
+        set_address.then(|| {
+            [
+                Command::PageAddressSet {
+                    address: self.mode.page(),
+                },
+                Command::ColumnAddressSet {
+                    address: self.column_offset() + (self.mode.column * CHAR_WIDTH) as u8,
+                },
+            ]
+        })
+    }
+}
+
+/// ---- Functionality of the terminal mode ----
+/// ============================================
+///
+/// In this mode, the driver acts as a text console and implements [`core::fmt::Write`].
+impl<DI, SPECS, const WIDTH: usize, const HEIGHT: usize, const PAGES: usize>
+    ST7565<DI, SPECS, TerminalMode, WIDTH, HEIGHT, PAGES>
+where
+    DI: WriteOnlyDataCommand,
+    SPECS: DisplaySpecs<WIDTH, HEIGHT, PAGES>,
+{
+    /// Clears the display and moves the cursor to the top left corner.
+    ///
+    /// Should be called once after entering the terminal mode.
+    pub fn clear(&mut self) -> Result<(), DisplayError> {
+        self.mode = TerminalMode::new(ram_pages(SPECS::CONTROLLER));
+        for page in 0..self.mode.ram_pages {
+            self.clear_page(page)?;
+        }
+        self.set_line_offset(0)
+    }
+
+    /// Prints the given text at the cursor position.
+    ///
+    /// `'\n'` moves the cursor to the next row and `'\r'` to the start of the row.
+    /// Rows that are too long wrap around.
+    pub fn print(&mut self, text: &str) -> Result<(), DisplayError> {
+        for c in text.chars() {
+            match c {
+                '\n' => self.newline()?,
+                '\r' => self.mode.carriage_return(),
+                c => self.print_char(c)?,
+            }
+        }
+        Ok(())
+    }
+
+    fn print_char(&mut self, c: char) -> Result<(), DisplayError> {
+        if self.mode.column >= Self::COLUMNS {
+            self.newline()?;
+        }
+
+        if let Some(commands) = self.cursor_address() {
+            self.interface
+                .send_command_sequence(SPECS::CONTROLLER, &commands)?;
+        }
+        self.interface.send_data(U8(&glyph(c)))?;
+        self.mode.advance();
+
+        Ok(())
+    }
+
+    fn newline(&mut self) -> Result<(), DisplayError> {
+        if let Some((page, line_offset)) = self.mode.newline(Self::ROWS) {
+            self.clear_page(page)?;
+            self.set_line_offset(line_offset)?;
+        }
+        Ok(())
+    }
+
+    fn clear_page(&mut self, page: u8) -> Result<(), DisplayError> {
+        self.mode.address_valid = false;
+        self.interface.send_page_data(
+            SPECS::CONTROLLER,
+            Some(page),
+            self.column_offset(),
+            U8(&[0; WIDTH]),
+        )
+    }
+}
+
+impl<DI, SPECS, const WIDTH: usize, const HEIGHT: usize, const PAGES: usize> core::fmt::Write
+    for ST7565<DI, SPECS, TerminalMode, WIDTH, HEIGHT, PAGES>
+where
+    DI: WriteOnlyDataCommand,
+    SPECS: DisplaySpecs<WIDTH, HEIGHT, PAGES>,
+{
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.print(s).map_err(|_| core::fmt::Error)
+    }
+}
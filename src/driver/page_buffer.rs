@@ -1,7 +1,223 @@
+use core::ops::Range;
+
+use display_interface::DisplayError;
+
+use crate::FlushError;
+
+/// The maximum number of disjoint dirty spans per page.
+///
+/// If more spans are required, the two closest spans get merged.
+const MAX_SPANS: usize = 4;
+
+/// The cost of sending a span separately instead of merging it with the previous one,
+/// in bytes.
+///
+/// Every span requires a two byte [`ColumnAddressSet`](crate::command::Command::ColumnAddressSet)
+/// command and an additional switch between command and data transfers.
+const SPAN_OVERHEAD: usize = 4;
+
+/// The columns of a page that are not synchronized with the display,
+/// as sorted and disjoint spans of `(start, end)`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) struct DirtySpans {
+    spans: [(usize, usize); MAX_SPANS],
+    len: usize,
+}
+
+impl DirtySpans {
+    /// No dirty columns
+    pub const fn new() -> Self {
+        Self {
+            spans: [(0, 0); MAX_SPANS],
+            len: 0,
+        }
+    }
+
+    /// All columns of a page with the given width are dirty
+    pub const fn full(width: usize) -> Self {
+        let mut spans = Self::new();
+        spans.spans[0] = (0, width);
+        spans.len = 1;
+        spans
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn spans(&self) -> &[(usize, usize)] {
+        &self.spans[..self.len]
+    }
+
+    /// Marks the columns `start..end` as dirty.
+    pub fn add(&mut self, start: usize, end: usize) {
+        if start >= end {
+            return;
+        }
+
+        let mut added = (start, end);
+        let mut result = [(0, 0); MAX_SPANS + 1];
+        let mut len = 0;
+        let mut inserted = false;
+
+        for &(start, end) in self.spans() {
+            if end < added.0 {
+                result[len] = (start, end);
+                len += 1;
+            } else if added.1 < start {
+                if !inserted {
+                    result[len] = added;
+                    len += 1;
+                    inserted = true;
+                }
+                result[len] = (start, end);
+                len += 1;
+            } else {
+                // Overlapping or adjacent
+                added = (added.0.min(start), added.1.max(end));
+            }
+        }
+        if !inserted {
+            result[len] = added;
+            len += 1;
+        }
+
+        if len > MAX_SPANS {
+            let closest = (0..len - 1)
+                .min_by_key(|&i| result[i + 1].0 - result[i].1)
+                .unwrap_or(0);
+            result[closest].1 = result[closest + 1].1;
+            result.copy_within(closest + 2..len, closest + 1);
+            len -= 1;
+        }
+
+        self.spans[..len].copy_from_slice(&result[..len]);
+        self.len = len;
+    }
+
+    /// The union of both span sets
+    pub fn union(mut self, other: &Self) -> Self {
+        for &(start, end) in other.spans() {
+            self.add(start, end);
+        }
+        self
+    }
+
+    /// Merges all spans that are cheaper to send together than separately
+    fn merge_close_spans(&self) -> Self {
+        let mut merged = Self::new();
+        for &(start, end) in self.spans() {
+            match merged.len.checked_sub(1) {
+                Some(last) if start - merged.spans[last].1 <= SPAN_OVERHEAD => {
+                    merged.spans[last].1 = end;
+                }
+                _ => {
+                    merged.spans[merged.len] = (start, end);
+                    merged.len += 1;
+                }
+            }
+        }
+        merged
+    }
+
+    /// The column ranges that have to be sent on a page with the given width.
+    ///
+    /// Spans that are cheaper to send together get merged.
+    pub fn transfer_ranges(&self, width: usize) -> impl Iterator<Item = Range<usize>> {
+        let merged = self.merge_close_spans();
+
+        (0..merged.len).filter_map(move |i| {
+            let (start, end) = merged.spans[i];
+            let end = end.min(width);
+            (start < end).then_some(start..end)
+        })
+    }
+}
+
+/// A transfer of buffer data into the display RAM.
+pub(crate) struct PageTransfer<D> {
+    /// The page of the display the data belongs to
+    pub page: usize,
+    /// The page of the display RAM, if it has to be selected before the transfer
+    pub address: Option<u8>,
+    /// The column of the display RAM of the first byte
+    pub column: u8,
+    /// The data to send
+    pub data: D,
+}
+
+impl PageTransfer<Range<usize>> {
+    /// The transfers of the given column ranges of a page.
+    ///
+    /// Only the first transfer selects the page of the display RAM,
+    /// the following ones stay on it.
+    pub fn for_ranges(
+        page: usize,
+        address: usize,
+        column_offset: u8,
+        ranges: impl Iterator<Item = Range<usize>>,
+    ) -> impl Iterator<Item = Self> {
+        ranges
+            .enumerate()
+            .map(move |(transfer, columns)| PageTransfer {
+                page,
+                address: (transfer == 0).then_some(address as u8),
+                column: column_offset + columns.start as u8,
+                data: columns,
+            })
+    }
+}
+
+impl<D> PageTransfer<D> {
+    /// Replaces the data of the transfer
+    pub fn map<E>(self, f: impl FnOnce(D) -> E) -> PageTransfer<E> {
+        PageTransfer {
+            page: self.page,
+            address: self.address,
+            column: self.column,
+            data: f(self.data),
+        }
+    }
+
+    /// The error of a flush that failed on this transfer
+    pub fn error(&self, error: DisplayError) -> FlushError {
+        FlushError {
+            page: self.page as u8,
+            error,
+        }
+    }
+}
+
 #[derive(Copy, Clone)]
 pub(crate) struct BufferPage<const WIDTH: usize> {
     pub data: [u8; WIDTH],
-    pub dirty: Option<(usize, usize)>,
+    pub dirty: DirtySpans,
+    /// The content of the display is unknown, so the dirty spans
+    /// must not be reduced by comparing them against a [`ShadowBuffer`]
+    pub forced: bool,
+}
+
+impl<const WIDTH: usize> BufferPage<WIDTH> {
+    /// The transfers required to send the given spans of the page
+    /// into the given page of the display RAM.
+    ///
+    /// Spans that are cheaper to send together get merged.
+    pub fn transfers(
+        &self,
+        page: usize,
+        address: usize,
+        column_offset: u8,
+        dirty: DirtySpans,
+    ) -> impl Iterator<Item = PageTransfer<&[u8]>> + '_ {
+        PageTransfer::for_ranges(page, address, column_offset, dirty.transfer_ranges(WIDTH))
+            .map(move |transfer| transfer.map(|columns| &self.data[columns]))
+    }
+
+    /// Marks the page as synchronized with the display.
+    pub fn mark_clean(&mut self) {
+        self.dirty = DirtySpans::new();
+        self.forced = false;
+    }
 }
 
 /// A graphics page buffer, required for graphics mode.
@@ -19,7 +235,8 @@ impl<const WIDTH: usize, const PAGES: usize> GraphicsPageBuffer<WIDTH, PAGES> {
             // Fill with full dirty flags to force an initial synchronization
             pages: [BufferPage {
                 data: [0; WIDTH],
-                dirty: Some((0, WIDTH)),
+                dirty: DirtySpans::full(WIDTH),
+                forced: true,
             }; PAGES],
         }
     }
@@ -28,7 +245,8 @@ impl<const WIDTH: usize, const PAGES: usize> GraphicsPageBuffer<WIDTH, PAGES> {
     /// all data at next [`flush()`](crate::ST7565::flush).
     pub fn mark_dirty(&mut self) {
         for page in &mut self.pages {
-            page.dirty = Some((0, WIDTH));
+            page.dirty = DirtySpans::full(WIDTH);
+            page.forced = true;
         }
     }
 }
@@ -38,3 +256,158 @@ impl<const WIDTH: usize, const PAGES: usize> Default for GraphicsPageBuffer<WIDT
         Self::new()
     }
 }
+
+#[derive(Copy, Clone)]
+pub(crate) struct GrayscaleBufferPage<const WIDTH: usize> {
+    /// The low and the high bit of the gray level of every pixel
+    pub planes: [[u8; WIDTH]; 2],
+    pub dirty: DirtySpans,
+}
+
+impl<const WIDTH: usize> GrayscaleBufferPage<WIDTH> {
+    /// The display data of the given columns.
+    ///
+    /// The ST7571 expects both bit planes of a column right after each other.
+    pub fn column_data(&self, columns: Range<usize>) -> impl Iterator<Item = u8> + '_ {
+        let [low, high] = &self.planes;
+        low[columns.clone()]
+            .iter()
+            .zip(&high[columns])
+            .flat_map(|(&low, &high)| IntoIterator::into_iter([low, high]))
+    }
+
+    /// The transfers required to send the dirty spans of the page
+    /// into the same page of the display RAM.
+    pub fn transfers(
+        &self,
+        page: usize,
+        column_offset: u8,
+    ) -> impl Iterator<Item = PageTransfer<impl Iterator<Item = u8> + '_>> + '_ {
+        PageTransfer::for_ranges(page, page, column_offset, self.dirty.transfer_ranges(WIDTH))
+            .map(move |transfer| transfer.map(|columns| self.column_data(columns)))
+    }
+}
+
+/// A grayscale page buffer with two bit planes, required for grayscale mode.
+///
+/// Uses twice the memory of a [`GraphicsPageBuffer`].
+///
+/// Needs to be provided by the user.
+#[derive(Clone)]
+pub struct GrayscalePageBuffer<const WIDTH: usize, const PAGES: usize> {
+    pub(crate) pages: [GrayscaleBufferPage<WIDTH>; PAGES],
+}
+
+impl<const WIDTH: usize, const PAGES: usize> GrayscalePageBuffer<WIDTH, PAGES> {
+    /// Creates a grayscale page buffer.
+    pub const fn new() -> Self {
+        Self {
+            // Fill with full dirty flags to force an initial synchronization
+            pages: [GrayscaleBufferPage {
+                planes: [[0; WIDTH]; 2],
+                dirty: DirtySpans::full(WIDTH),
+            }; PAGES],
+        }
+    }
+
+    /// Marks the entire buffer as dirty, causing a full retransmission of
+    /// all data at next [`flush()`](crate::ST7565::flush).
+    pub fn mark_dirty(&mut self) {
+        for page in &mut self.pages {
+            page.dirty = DirtySpans::full(WIDTH);
+        }
+    }
+}
+
+impl<const WIDTH: usize, const PAGES: usize> Default for GrayscalePageBuffer<WIDTH, PAGES> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A copy of the data that was last sent to the display.
+///
+/// Optional for graphics mode, see
+/// [`with_shadow_buffer()`](crate::ST7565::with_shadow_buffer).
+/// Doubles the memory required for graphics, but reduces the bus traffic
+/// if pixels get changed and then changed back before the next flush.
+#[derive(Clone)]
+pub struct ShadowBuffer<const WIDTH: usize, const PAGES: usize> {
+    pages: [[u8; WIDTH]; PAGES],
+}
+
+impl<const WIDTH: usize, const PAGES: usize> ShadowBuffer<WIDTH, PAGES> {
+    /// Creates a shadow buffer.
+    pub const fn new() -> Self {
+        Self {
+            pages: [[0; WIDTH]; PAGES],
+        }
+    }
+
+    /// The dirty spans of the given page, reduced to the bytes that differ
+    /// from the display content
+    pub(crate) fn changed(&self, index: usize, page: &BufferPage<WIDTH>) -> DirtySpans {
+        if page.forced {
+            return page.dirty;
+        }
+
+        let shadow = &self.pages[index];
+        let mut changed = DirtySpans::new();
+        for &(start, end) in page.dirty.spans() {
+            let end = end.min(WIDTH);
+            let mut run_start = None;
+            let columns = page.data[start..end].iter().zip(&shadow[start..end]);
+            for (column, (new, old)) in (start..).zip(columns) {
+                match (new != old, run_start) {
+                    (true, None) => run_start = Some(column),
+                    (false, Some(run)) => {
+                        changed.add(run, column);
+                        run_start = None;
+                    }
+                    _ => {}
+                }
+            }
+            if let Some(run) = run_start {
+                changed.add(run, end);
+            }
+        }
+        changed
+    }
+
+    /// Stores the content of a page that was flushed successfully
+    pub(crate) fn update(&mut self, index: usize, page: &BufferPage<WIDTH>) {
+        self.pages[index] = page.data;
+    }
+}
+
+impl<const WIDTH: usize, const PAGES: usize> Default for ShadowBuffer<WIDTH, PAGES> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A buffer that holds a band of `BAND_PAGES` pages, required for page band mode.
+///
+/// Uses `WIDTH * BAND_PAGES` bytes instead of the `WIDTH * PAGES` bytes of a
+/// full [`GraphicsPageBuffer`], at the cost of rendering every frame in multiple passes.
+///
+/// Needs to be provided by the user.
+#[derive(Clone)]
+pub struct PageBandBuffer<const WIDTH: usize, const BAND_PAGES: usize> {
+    pub(crate) pages: [[u8; WIDTH]; BAND_PAGES],
+}
+
+impl<const WIDTH: usize, const BAND_PAGES: usize> PageBandBuffer<WIDTH, BAND_PAGES> {
+    /// Creates a page band buffer.
+    pub const fn new() -> Self {
+        Self {
+            pages: [[0; WIDTH]; BAND_PAGES],
+        }
+    }
+}
+
+impl<const WIDTH: usize, const BAND_PAGES: usize> Default for PageBandBuffer<WIDTH, BAND_PAGES> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
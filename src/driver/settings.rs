@@ -0,0 +1,59 @@
+use crate::{
+    types::{
+        BoosterRatio, Contrast, LcdBias, Orientation, PowerControlMode, PowerState, RegulatorRatio,
+    },
+    DisplaySpecs,
+};
+
+/// The configuration and state of the driver circuits of the display.
+///
+/// Initialized from the [`DisplaySpecs`] and modified through the setters
+/// of the driver, so that a reset can restore the values set by the user.
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct DisplaySettings {
+    pub bias: LcdBias,
+    pub booster_ratio: BoosterRatio,
+    pub regulator_ratio: RegulatorRatio,
+    pub contrast: Contrast,
+    pub power_control: PowerControlMode,
+    pub power_state: PowerState,
+    /// Requested display on/off state; only applied while [`PowerState::Active`]
+    pub display_on: bool,
+    /// Requested all-points state; only applied while [`PowerState::Active`]
+    pub all_points_on: bool,
+    pub orientation: Orientation,
+    /// The line offset requested by the user, independent of the orientation
+    pub line_offset: u8,
+    /// Whether the grayscale mode of the ST7571 gets selected on reset
+    pub grayscale: bool,
+}
+
+impl DisplaySettings {
+    pub fn from_specs<SPECS, const WIDTH: usize, const HEIGHT: usize, const PAGES: usize>() -> Self
+    where
+        SPECS: DisplaySpecs<WIDTH, HEIGHT, PAGES>,
+    {
+        Self {
+            bias: if SPECS::BIAS_MODE_1 {
+                LcdBias::Mode1
+            } else {
+                LcdBias::Mode0
+            },
+            booster_ratio: SPECS::BOOSTER_RATIO,
+            regulator_ratio: RegulatorRatio::new_masked(SPECS::VOLTAGE_REGULATOR_RESISTOR_RATIO),
+            contrast: Contrast::new_masked(SPECS::ELECTRONIC_VOLUME),
+            power_control: SPECS::POWER_CONTROL,
+            power_state: PowerState::Active,
+            display_on: false,
+            all_points_on: false,
+            orientation: Orientation::Normal,
+            line_offset: 0,
+            grayscale: false,
+        }
+    }
+
+    /// Whether the display is in one of its power save states
+    pub fn is_powered_down(&self) -> bool {
+        self.power_state != PowerState::Active
+    }
+}
@@ -0,0 +1,520 @@
+//! An in-memory emulation of the ST7565 controller.
+//!
+//! The [`Emulator`] implements [`WriteOnlyDataCommand`] and [`ReadableDataCommand`]
+//! and can therefore be used as the display interface of the driver. It keeps track of the internal state of the
+//! controller and computes what the display would actually show, which makes it
+//! possible to test display code without hardware.
+//!
+//! ```rust
+//! # use st7565::{displays::DOGM132W5, emulator::Emulator, GraphicsPageBuffer, ST7565};
+//! # use embedded_graphics::{pixelcolor::BinaryColor, prelude::*};
+//! # struct Pin;
+//! # impl embedded_hal::digital::ErrorType for Pin { type Error = core::convert::Infallible; }
+//! # impl embedded_hal::digital::OutputPin for Pin {
+//! #     fn set_low(&mut self) -> Result<(), Self::Error> { Ok(()) }
+//! #     fn set_high(&mut self) -> Result<(), Self::Error> { Ok(()) }
+//! # }
+//! # struct Delay;
+//! # impl embedded_hal::delay::DelayNs for Delay { fn delay_ns(&mut self, _ns: u32) {} }
+//! # let (mut disp_rst, mut delay) = (Pin, Delay);
+//! let mut emulator = Emulator::new(DOGM132W5);
+//!
+//! let mut page_buffer = GraphicsPageBuffer::new();
+//! let mut disp = ST7565::new(&mut emulator, DOGM132W5).into_graphics_mode(&mut page_buffer);
+//! disp.reset(&mut disp_rst, &mut delay).unwrap();
+//! Pixel(Point::new(3, 5), BinaryColor::On).draw(&mut disp).unwrap();
+//! disp.flush().unwrap();
+//! disp.set_display_on(true).unwrap();
+//!
+//! assert!(emulator.pixel(3, 5));
+//! assert!(!emulator.pixel(4, 5));
+//! ```
+
+use core::marker::PhantomData;
+
+use display_interface::{DataFormat, DisplayError, WriteOnlyDataCommand};
+
+use crate::{
+    command::{Command, DecodeError, MAX_COMMAND_LEN},
+    interface::ReadableDataCommand,
+    types::Controller,
+    DisplaySpecs,
+};
+
+/// The number of columns of the display RAM
+pub const RAM_COLUMNS: usize = 132;
+/// The number of pages of the display RAM, including the icon page
+pub const RAM_PAGES: usize = 9;
+/// The number of lines the common drivers cycle through, excluding the icon line
+const COMMON_LINES: u8 = 64;
+
+/// The registers of the controller
+#[derive(Copy, Clone)]
+struct Registers {
+    page: u8,
+    column: u8,
+    read_modify_write_column: Option<u8>,
+    start_line: u8,
+    adc_reverse: bool,
+    com_reverse: bool,
+    inverted: bool,
+    all_points_on: bool,
+    display_on: bool,
+    bias_mode_1: bool,
+    power_control: u8,
+    regulator_ratio: u8,
+    contrast: u8,
+    booster_ratio: u8,
+    static_indicator: Option<u8>,
+    advanced_program_control: Option<u8>,
+}
+
+impl Registers {
+    /// The state after a hardware reset
+    const RESET: Self = Self {
+        page: 0,
+        column: 0,
+        read_modify_write_column: None,
+        start_line: 0,
+        adc_reverse: false,
+        com_reverse: false,
+        inverted: false,
+        all_points_on: false,
+        display_on: false,
+        bias_mode_1: false,
+        power_control: 0,
+        regulator_ratio: 0b100,
+        contrast: 0b100000,
+        booster_ratio: 0,
+        static_indicator: None,
+        advanced_program_control: None,
+    };
+
+    /// Applies the reset command.
+    ///
+    /// Other than a hardware reset, this keeps the display mode settings.
+    fn software_reset(&mut self) {
+        *self = Self {
+            adc_reverse: self.adc_reverse,
+            inverted: self.inverted,
+            all_points_on: self.all_points_on,
+            display_on: self.display_on,
+            bias_mode_1: self.bias_mode_1,
+            booster_ratio: self.booster_ratio,
+            ..Self::RESET
+        };
+    }
+}
+
+/// An in-memory emulation of the ST7565 controller.
+///
+/// A new emulator is in the same state as the controller after a hardware reset,
+/// with the display RAM cleared.
+///
+/// The wiring between the controller and the glass is derived from the display
+/// specification: with the ADC and common output mode directions of the specification,
+/// the content of the display RAM shows up upright on the glass.
+///
+/// Command bytes are decoded with [`Command::decode_for()`] for the controller of the
+/// display specification. The ST7571, with its 128 lines and grayscale mode, is not
+/// emulated; creating an emulator for it fails to compile. Only [`DataFormat::U8`] and [`DataFormat::U8Iter`] are
+/// supported; unknown commands are rejected with [`DisplayError::InvalidFormatError`].
+pub struct Emulator<SPECS, const WIDTH: usize, const HEIGHT: usize, const PAGES: usize> {
+    display_specs: PhantomData<SPECS>,
+    ddram: [[u8; RAM_COLUMNS]; RAM_PAGES],
+    registers: Registers,
+    /// The bytes of a command that has not been completely received yet
+    pending_command: [u8; MAX_COMMAND_LEN],
+    pending_len: usize,
+    read_latch: u8,
+}
+
+impl<SPECS, const WIDTH: usize, const HEIGHT: usize, const PAGES: usize>
+    Emulator<SPECS, WIDTH, HEIGHT, PAGES>
+where
+    SPECS: DisplaySpecs<WIDTH, HEIGHT, PAGES>,
+{
+    const CONTROLLER_EMULATED: () = assert!(
+        !matches!(SPECS::CONTROLLER, Controller::St7571),
+        "The emulator does not support the ST7571"
+    );
+
+    /// Creates an emulator for the given display.
+    ///
+    /// Displays with an ST7571 are rejected at compile time:
+    ///
+    /// ```compile_fail
+    /// # use st7565::{displays::ST7571_128128, emulator::Emulator};
+    /// let emulator = Emulator::new(ST7571_128128);
+    /// ```
+    pub fn new(_display_specs: SPECS) -> Self {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::CONTROLLER_EMULATED;
+
+        Self {
+            display_specs: PhantomData,
+            ddram: [[0; RAM_COLUMNS]; RAM_PAGES],
+            registers: Registers::RESET,
+            pending_command: [0; MAX_COMMAND_LEN],
+            pending_len: 0,
+            read_latch: 0,
+        }
+    }
+
+    /// Performs a hardware reset, like a pulse on the reset pin.
+    ///
+    /// The display RAM is retained.
+    pub fn hardware_reset(&mut self) {
+        self.registers = Registers::RESET;
+        self.pending_len = 0;
+    }
+
+    /// What the display currently shows at the given position.
+    ///
+    /// Returns `true` for a dark pixel.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the position is outside of the display.
+    pub fn pixel(&self, x: usize, y: usize) -> bool {
+        assert!(
+            x < WIDTH && y < HEIGHT,
+            "Pixel ({}, {}) is out of bounds",
+            x,
+            y
+        );
+
+        let regs = &self.registers;
+
+        if !regs.display_on {
+            return false;
+        }
+        if regs.all_points_on {
+            return true;
+        }
+
+        // The glass is wired so that the display specification shows the content upright
+        let segment = if SPECS::FLIP_COLUMNS {
+            RAM_COLUMNS - 1 - usize::from(SPECS::COLUMN_OFFSET) - x
+        } else {
+            x + usize::from(SPECS::COLUMN_OFFSET)
+        };
+        let common = (y + usize::from(SPECS::ROW_OFFSET)) % usize::from(COMMON_LINES);
+        let common = if SPECS::FLIP_ROWS {
+            usize::from(COMMON_LINES) - 1 - common
+        } else {
+            common
+        };
+
+        let column = if regs.adc_reverse {
+            RAM_COLUMNS - 1 - segment
+        } else {
+            segment
+        };
+        let common = if regs.com_reverse {
+            usize::from(COMMON_LINES) - 1 - common
+        } else {
+            common
+        };
+        let line = (usize::from(regs.start_line) + common) % usize::from(COMMON_LINES);
+
+        let value = self.ddram[line / 8][column] & (1 << (line % 8)) != 0;
+        value != regs.inverted
+    }
+
+    /// What the display currently shows, as rows of pixels.
+    ///
+    /// A value of `true` represents a dark pixel.
+    pub fn pixels(&self) -> [[bool; WIDTH]; HEIGHT] {
+        let mut pixels = [[false; WIDTH]; HEIGHT];
+        for (y, row) in pixels.iter_mut().enumerate() {
+            for (x, pixel) in row.iter_mut().enumerate() {
+                *pixel = self.pixel(x, y);
+            }
+        }
+        pixels
+    }
+
+    /// The content of the display RAM, including the icon page
+    pub fn ddram(&self) -> &[[u8; RAM_COLUMNS]; RAM_PAGES] {
+        &self.ddram
+    }
+
+    /// The current page address
+    pub fn page_address(&self) -> u8 {
+        self.registers.page
+    }
+
+    /// The current column address
+    pub fn column_address(&self) -> u8 {
+        self.registers.column
+    }
+
+    /// The display RAM line that is shown at the top of the display
+    pub fn start_line(&self) -> u8 {
+        self.registers.start_line
+    }
+
+    /// Whether the ADC direction is reversed
+    pub fn adc_reverse(&self) -> bool {
+        self.registers.adc_reverse
+    }
+
+    /// Whether the common output mode is reversed
+    pub fn com_reverse(&self) -> bool {
+        self.registers.com_reverse
+    }
+
+    /// Whether the display is inverted
+    pub fn inverted(&self) -> bool {
+        self.registers.inverted
+    }
+
+    /// Whether all points of the display are forced on
+    pub fn all_points_on(&self) -> bool {
+        self.registers.all_points_on
+    }
+
+    /// Whether the display output is turned on
+    pub fn display_on(&self) -> bool {
+        self.registers.display_on
+    }
+
+    /// Whether the display is in power save mode
+    pub fn power_save(&self) -> bool {
+        !self.registers.display_on && self.registers.all_points_on
+    }
+
+    /// Whether LCD bias mode "1" is selected
+    pub fn bias_mode_1(&self) -> bool {
+        self.registers.bias_mode_1
+    }
+
+    /// The power control register; booster, regulator and follower in bits 2 to 0
+    pub fn power_control(&self) -> u8 {
+        self.registers.power_control
+    }
+
+    /// The ratio of the internal voltage regulator resistors
+    pub fn regulator_ratio(&self) -> u8 {
+        self.registers.regulator_ratio
+    }
+
+    /// The contrast, also called electronic volume
+    pub fn contrast(&self) -> u8 {
+        self.registers.contrast
+    }
+
+    /// The raw value of the booster ratio register
+    pub fn booster_ratio(&self) -> u8 {
+        self.registers.booster_ratio
+    }
+
+    /// The raw mode of the static indicator, if it is turned on
+    pub fn static_indicator(&self) -> Option<u8> {
+        self.registers.static_indicator
+    }
+
+    /// The raw value of the advanced program control register of the UC1701, if it was written
+    pub fn advanced_program_control(&self) -> Option<u8> {
+        self.registers.advanced_program_control
+    }
+
+    fn process_command(&mut self, byte: u8) -> Result<(), DisplayError> {
+        self.pending_command[self.pending_len] = byte;
+        self.pending_len += 1;
+        let bytes = &self.pending_command[..self.pending_len];
+
+        let command = match Command::decode_for(SPECS::CONTROLLER, bytes) {
+            // Wait for the remaining bytes of the command
+            Err(DecodeError::Truncated { .. }) => return Ok(()),
+            result => result,
+        };
+        let argument = bytes.get(1).copied().unwrap_or_default();
+        self.pending_len = 0;
+        let (command, _) = command.map_err(|_| DisplayError::InvalidFormatError)?;
+
+        let regs = &mut self.registers;
+        match command {
+            Command::DisplayOnOff { on } => regs.display_on = on,
+            Command::DisplayStartLineSet { address } => regs.start_line = address,
+            Command::PageAddressSet { address } => regs.page = address,
+            Command::ColumnAddressSet { address } => regs.column = address,
+            Command::AdcSelect { reverse } => regs.adc_reverse = reverse,
+            Command::DisplayNormalReverse { reverse } => regs.inverted = reverse,
+            Command::DisplayAllPoints { on } => regs.all_points_on = on,
+            Command::LcdBiasSet { bias_mode_1 } => regs.bias_mode_1 = bias_mode_1,
+            Command::ReadModifyWrite => regs.read_modify_write_column = Some(regs.column),
+            Command::End => {
+                if let Some(column) = regs.read_modify_write_column.take() {
+                    regs.column = column;
+                }
+            }
+            Command::Reset => regs.software_reset(),
+            Command::CommonOutputModeSelect { reverse } => regs.com_reverse = reverse,
+            Command::PowerControlSet { mode } => {
+                regs.power_control = (mode.booster_circuit as u8) << 2
+                    | (mode.voltage_regulator_circuit as u8) << 1
+                    | mode.voltage_follower_circuit as u8
+            }
+            Command::VoltageRegulatorInternalResistorSet { resistor_ratio } => {
+                regs.regulator_ratio = resistor_ratio
+            }
+            Command::ElectronicVolumeSet { volume_value } => regs.contrast = volume_value,
+            Command::StaticIndicatorSet { mode } => {
+                regs.static_indicator = mode.map(|_| argument & 0b00000011)
+            }
+            Command::BoosterRatioSet { .. } => regs.booster_ratio = argument & 0b00000011,
+            Command::AdvancedProgramControl { .. } => {
+                regs.advanced_program_control = Some(argument)
+            }
+            Command::Nop => {}
+            // Only decoded for the ST7571, which is not emulated
+            Command::OscillatorOn
+            | Command::DisplayModeSet { .. }
+            | Command::GrayLevelSet { .. } => return Err(DisplayError::InvalidFormatError),
+        }
+
+        Ok(())
+    }
+
+    fn process_data(&mut self, byte: u8) {
+        let regs = &mut self.registers;
+
+        let page = usize::from(regs.page);
+        let column = usize::from(regs.column);
+        if page < RAM_PAGES && column < RAM_COLUMNS {
+            self.ddram[page][column] = byte;
+        }
+
+        regs.column = regs.column.saturating_add(1);
+    }
+
+    fn read_data_byte(&mut self) -> u8 {
+        let regs = &mut self.registers;
+
+        let value = self.read_latch;
+
+        let page = usize::from(regs.page);
+        let column = usize::from(regs.column);
+        if page < RAM_PAGES && column < RAM_COLUMNS {
+            self.read_latch = self.ddram[page][column];
+        }
+
+        // Reading does not move the column in read-modify-write mode
+        if regs.read_modify_write_column.is_none() {
+            regs.column = regs.column.saturating_add(1);
+        }
+
+        value
+    }
+
+    fn status_byte(&self) -> u8 {
+        // The ADC and ON/OFF flags are active low; the emulator is never busy or resetting
+        let regs = &self.registers;
+        ((!regs.adc_reverse as u8) << 6) | ((!regs.display_on as u8) << 5)
+    }
+
+    /// Drops a command that has not been completely received.
+    ///
+    /// Returns an error if there was one.
+    fn abort_pending_command(&mut self) -> Result<(), DisplayError> {
+        match core::mem::take(&mut self.pending_len) {
+            0 => Ok(()),
+            _ => Err(DisplayError::InvalidFormatError),
+        }
+    }
+
+    fn read_data_bytes(&mut self, buf: &mut [u8]) -> Result<(), DisplayError> {
+        self.abort_pending_command()?;
+
+        buf.iter_mut().for_each(|b| *b = self.read_data_byte());
+        Ok(())
+    }
+
+    fn process_commands(&mut self, cmd: DataFormat<'_>) -> Result<(), DisplayError> {
+        match cmd {
+            DataFormat::U8(bytes) => bytes.iter().try_for_each(|&b| self.process_command(b)),
+            DataFormat::U8Iter(bytes) => {
+                for byte in bytes {
+                    self.process_command(byte)?;
+                }
+                Ok(())
+            }
+            _ => Err(DisplayError::DataFormatNotImplemented),
+        }
+    }
+
+    fn process_data_bytes(&mut self, buf: DataFormat<'_>) -> Result<(), DisplayError> {
+        // Data in the middle of a multi byte command is invalid
+        self.abort_pending_command()?;
+
+        match buf {
+            DataFormat::U8(bytes) => bytes.iter().for_each(|&b| self.process_data(b)),
+            DataFormat::U8Iter(bytes) => {
+                for byte in bytes {
+                    self.process_data(byte);
+                }
+            }
+            _ => return Err(DisplayError::DataFormatNotImplemented),
+        }
+
+        Ok(())
+    }
+}
+
+impl<SPECS, const WIDTH: usize, const HEIGHT: usize, const PAGES: usize> WriteOnlyDataCommand
+    for Emulator<SPECS, WIDTH, HEIGHT, PAGES>
+where
+    SPECS: DisplaySpecs<WIDTH, HEIGHT, PAGES>,
+{
+    fn send_commands(&mut self, cmd: DataFormat<'_>) -> Result<(), DisplayError> {
+        self.process_commands(cmd)
+    }
+
+    fn send_data(&mut self, buf: DataFormat<'_>) -> Result<(), DisplayError> {
+        self.process_data_bytes(buf)
+    }
+}
+
+impl<SPECS, const WIDTH: usize, const HEIGHT: usize, const PAGES: usize> WriteOnlyDataCommand
+    for &mut Emulator<SPECS, WIDTH, HEIGHT, PAGES>
+where
+    SPECS: DisplaySpecs<WIDTH, HEIGHT, PAGES>,
+{
+    fn send_commands(&mut self, cmd: DataFormat<'_>) -> Result<(), DisplayError> {
+        self.process_commands(cmd)
+    }
+
+    fn send_data(&mut self, buf: DataFormat<'_>) -> Result<(), DisplayError> {
+        self.process_data_bytes(buf)
+    }
+}
+
+impl<SPECS, const WIDTH: usize, const HEIGHT: usize, const PAGES: usize> ReadableDataCommand
+    for Emulator<SPECS, WIDTH, HEIGHT, PAGES>
+where
+    SPECS: DisplaySpecs<WIDTH, HEIGHT, PAGES>,
+{
+    fn read_data(&mut self, buf: &mut [u8]) -> Result<(), DisplayError> {
+        self.read_data_bytes(buf)
+    }
+
+    fn read_status(&mut self) -> Result<u8, DisplayError> {
+        Ok(self.status_byte())
+    }
+}
+
+impl<SPECS, const WIDTH: usize, const HEIGHT: usize, const PAGES: usize> ReadableDataCommand
+    for &mut Emulator<SPECS, WIDTH, HEIGHT, PAGES>
+where
+    SPECS: DisplaySpecs<WIDTH, HEIGHT, PAGES>,
+{
+    fn read_data(&mut self, buf: &mut [u8]) -> Result<(), DisplayError> {
+        self.read_data_bytes(buf)
+    }
+
+    fn read_status(&mut self) -> Result<u8, DisplayError> {
+        Ok(self.status_byte())
+    }
+}
@@ -1,5 +1,7 @@
 use display_interface::DisplayError;
 
+use crate::types::Status;
+
 /// Errors in this crate
 #[derive(Debug)]
 pub enum Error<PinE> {
@@ -8,3 +10,32 @@ pub enum Error<PinE> {
     /// Pin setting error
     Pin(PinE),
 }
+
+/// Error that occurred while flushing the graphics buffer to the display
+///
+/// All pages that were not transmitted successfully, including the failed
+/// one, stay dirty and get sent again at the next flush.
+#[derive(Debug)]
+pub struct FlushError {
+    /// The page whose transmission failed
+    pub page: u8,
+    /// The communication error
+    pub error: DisplayError,
+}
+
+impl From<FlushError> for DisplayError {
+    fn from(err: FlushError) -> Self {
+        err.error
+    }
+}
+
+/// Error of a display health check
+#[derive(Debug)]
+pub enum HealthCheckError {
+    /// Communication error
+    Comm(DisplayError),
+    /// The controller reported a state that differs from the configured one.
+    ///
+    /// Indicates a missing or broken display, or one that lost its configuration.
+    UnexpectedStatus(Status),
+}
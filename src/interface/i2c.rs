@@ -0,0 +1,155 @@
+//! A display interface for I2C capable controllers, like the ST7567A.
+//!
+//! ```rust
+//! # use st7565::{displays::ST7567A_12864, interface::i2c::I2cInterface, ST7565};
+//! # struct I2c;
+//! # impl embedded_hal::i2c::ErrorType for I2c { type Error = core::convert::Infallible; }
+//! # impl embedded_hal::i2c::I2c for I2c {
+//! #     fn transaction(&mut self, _: u8, _: &mut [embedded_hal::i2c::Operation<'_>]) -> Result<(), Self::Error> { Ok(()) }
+//! # }
+//! # let i2c = I2c;
+//! let interface = I2cInterface::new(i2c).with_address(0x3F);
+//! let disp = ST7565::new(interface, ST7567A_12864).into_raw_mode();
+//! ```
+//!
+//! Every transfer starts with a control byte. Its `A0` bit selects between
+//! commands and data; its `Co` bit is always cleared, so all following bytes of the
+//! transfer are of the same kind.
+
+#[cfg(feature = "async")]
+use display_interface::AsyncWriteOnlyDataCommand;
+use display_interface::{DataFormat, DisplayError, WriteOnlyDataCommand};
+use embedded_hal::i2c::{I2c, Operation};
+
+use super::next_chunk;
+
+/// The control byte that precedes commands
+const CONTROL_COMMANDS: u8 = 0b0000_0000;
+/// The control byte that precedes display data
+const CONTROL_DATA: u8 = 0b0100_0000;
+
+/// A display interface for an [`I2c`] bus.
+///
+/// Also implements [`display_interface::AsyncWriteOnlyDataCommand`] for async I2C buses,
+/// if the `async` feature is enabled.
+///
+/// Only [`DataFormat::U8`] and [`DataFormat::U8Iter`] are supported.
+pub struct I2cInterface<I2C> {
+    i2c: I2C,
+    address: u8,
+}
+
+impl<I2C> I2cInterface<I2C> {
+    /// The 7-bit address of the ST7567A
+    pub const DEFAULT_ADDRESS: u8 = 0x3F;
+
+    /// Creates an interface that talks to [`DEFAULT_ADDRESS`](Self::DEFAULT_ADDRESS)
+    pub fn new(i2c: I2C) -> Self {
+        Self {
+            i2c,
+            address: Self::DEFAULT_ADDRESS,
+        }
+    }
+
+    /// Changes the 7-bit address of the display
+    pub fn with_address(mut self, address: u8) -> Self {
+        self.address = address;
+        self
+    }
+
+    /// Releases the I2C bus
+    pub fn release(self) -> I2C {
+        self.i2c
+    }
+}
+
+impl<I2C> I2cInterface<I2C>
+where
+    I2C: I2c,
+{
+    fn write(&mut self, control: u8, format: DataFormat<'_>) -> Result<(), DisplayError> {
+        let address = self.address;
+        let mut write = |bytes: &[u8]| {
+            // Adjacent writes are sent without a restart in between
+            self.i2c
+                .transaction(
+                    address,
+                    &mut [Operation::Write(&[control]), Operation::Write(bytes)],
+                )
+                .map_err(|_| DisplayError::BusWriteError)
+        };
+
+        match format {
+            DataFormat::U8(bytes) => write(bytes),
+            DataFormat::U8Iter(bytes) => loop {
+                let (buffer, len) = next_chunk(bytes);
+                if len == 0 {
+                    return Ok(());
+                }
+                write(&buffer[..len])?;
+            },
+            _ => Err(DisplayError::DataFormatNotImplemented),
+        }
+    }
+}
+
+impl<I2C> WriteOnlyDataCommand for I2cInterface<I2C>
+where
+    I2C: I2c,
+{
+    fn send_commands(&mut self, cmd: DataFormat<'_>) -> Result<(), DisplayError> {
+        self.write(CONTROL_COMMANDS, cmd)
+    }
+
+    fn send_data(&mut self, buf: DataFormat<'_>) -> Result<(), DisplayError> {
+        self.write(CONTROL_DATA, buf)
+    }
+}
+
+#[cfg(feature = "async")]
+impl<I2C> I2cInterface<I2C>
+where
+    I2C: embedded_hal_async::i2c::I2c,
+{
+    async fn write_chunk_async(&mut self, control: u8, bytes: &[u8]) -> Result<(), DisplayError> {
+        self.i2c
+            .transaction(
+                self.address,
+                &mut [Operation::Write(&[control]), Operation::Write(bytes)],
+            )
+            .await
+            .map_err(|_| DisplayError::BusWriteError)
+    }
+
+    async fn write_async(
+        &mut self,
+        control: u8,
+        format: DataFormat<'_>,
+    ) -> Result<(), DisplayError> {
+        match format {
+            DataFormat::U8(bytes) => self.write_chunk_async(control, bytes).await,
+            DataFormat::U8Iter(bytes) => loop {
+                let (buffer, len) = next_chunk(bytes);
+                if len == 0 {
+                    return Ok(());
+                }
+                self.write_chunk_async(control, &buffer[..len]).await?;
+            },
+            _ => Err(DisplayError::DataFormatNotImplemented),
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+impl<I2C> AsyncWriteOnlyDataCommand for I2cInterface<I2C>
+where
+    I2C: embedded_hal_async::i2c::I2c,
+{
+    async fn send_commands(&mut self, cmd: DataFormat<'_>) -> Result<(), DisplayError> {
+        self.write_async(CONTROL_COMMANDS, cmd).await
+    }
+
+    async fn send_data(&mut self, buf: DataFormat<'_>) -> Result<(), DisplayError> {
+        self.write_async(CONTROL_DATA, buf).await
+    }
+}
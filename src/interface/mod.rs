@@ -0,0 +1,48 @@
+//! Display interfaces.
+//!
+//! The driver writes to the display through any [`WriteOnlyDataCommand`] interface.
+//! Some functionality additionally requires reading back the display RAM, which
+//! the ST7565 only supports on its parallel 6800 and 8080 buses.
+//!
+//! [`ParallelInterface`](parallel::ParallelInterface) implements those buses on
+//! general purpose pins. [`SpiInterface`](spi::SpiInterface) writes through an
+//! [`SpiDevice`](embedded_hal::spi::SpiDevice) and sends the address commands of every
+//! transfer in a single transaction. [`I2cInterface`](i2c::I2cInterface) supports
+//! I2C capable controllers like the ST7567A, and [`Spi9BitInterface`](spi_9bit::Spi9BitInterface)
+//! supports the 3-wire 9-bit SPI mode of controllers like the UC1701.
+
+use display_interface::{DisplayError, WriteOnlyDataCommand};
+
+pub mod i2c;
+pub mod parallel;
+pub mod spi;
+pub mod spi_9bit;
+
+/// A display interface that can also read from the display RAM.
+pub trait ReadableDataCommand: WriteOnlyDataCommand {
+    /// Reads bytes from the display data register.
+    ///
+    /// Like on the hardware, every read returns the content of the output latch and then
+    /// latches the byte at the current address. The first read after setting the address
+    /// is therefore a dummy read.
+    fn read_data(&mut self, buf: &mut [u8]) -> Result<(), DisplayError>;
+
+    /// Reads the status byte of the controller.
+    fn read_status(&mut self) -> Result<u8, DisplayError>;
+}
+
+/// The size of the buffer that data from iterators gets collected in
+pub(crate) const CHUNK_SIZE: usize = 32;
+
+/// Collects up to [`CHUNK_SIZE`] bytes of an iterator.
+///
+/// Returns the buffer and the number of bytes collected.
+pub(crate) fn next_chunk(bytes: &mut dyn Iterator<Item = u8>) -> ([u8; CHUNK_SIZE], usize) {
+    let mut buffer = [0; CHUNK_SIZE];
+    let mut len = 0;
+    for (slot, byte) in buffer.iter_mut().zip(bytes) {
+        *slot = byte;
+        len += 1;
+    }
+    (buffer, len)
+}
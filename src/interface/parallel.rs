@@ -0,0 +1,447 @@
+//! A bit-banged 8-bit parallel interface.
+//!
+//! Drives the 8080 or 6800 bus of the ST7565 through general purpose pins:
+//!
+//! ```rust
+//! # use st7565::{displays::DOGM132W5, interface::parallel::{DataBus, ParallelInterface}, ST7565};
+//! # struct Pin;
+//! # impl embedded_hal::digital::ErrorType for Pin { type Error = core::convert::Infallible; }
+//! # impl embedded_hal::digital::OutputPin for Pin {
+//! #     fn set_low(&mut self) -> Result<(), Self::Error> { Ok(()) }
+//! #     fn set_high(&mut self) -> Result<(), Self::Error> { Ok(()) }
+//! # }
+//! # let (d0, d1, d2, d3, d4, d5, d6, d7) = (Pin, Pin, Pin, Pin, Pin, Pin, Pin, Pin);
+//! # let (a0, wr, rd, cs) = (Pin, Pin, Pin, Pin);
+//! let bus = DataBus::new(d0, d1, d2, d3, d4, d5, d6, d7);
+//! let interface = ParallelInterface::new_8080(bus, a0, wr, rd, cs);
+//! let disp = ST7565::new(interface, DOGM132W5).into_raw_mode();
+//! ```
+//!
+//! With data pins that implement both [`OutputPin`] and [`InputPin`], the interface also
+//! implements [`ReadableDataCommand`], which enables status and display data reads.
+//! For reads, the data pins get driven high and are then read back, so they have to be
+//! configured as open drain outputs with pull-up resistors.
+//!
+//! Without a delay, the interface does not wait between pin changes, so the bus is only
+//! as slow as the pins toggle. At low supply voltages, the ST7565 requires a bus cycle
+//! time of up to 1000 ns, which the pins of fast microcontrollers easily undercut.
+//! Those need a delay through [`with_delay()`](ParallelInterface::with_delay), which
+//! holds each phase of the strobes for [`STROBE_PHASE_NS`].
+
+use display_interface::{DataFormat, DisplayError, WriteOnlyDataCommand};
+use embedded_hal::{
+    delay::DelayNs,
+    digital::{InputPin, OutputPin, PinState},
+};
+
+use super::ReadableDataCommand;
+
+/// The time in nanoseconds that each phase of the strobe gets held with a delay.
+///
+/// Two phases make up a bus cycle of 1000 ns, which meets the bus cycle time, the
+/// strobe pulse widths and the read access time of the ST7565 down to 1.8 V.
+pub const STROBE_PHASE_NS: u32 = 500;
+
+/// A delay that does not wait, used by a [`ParallelInterface`] without a delay
+#[derive(Debug, Copy, Clone, Default)]
+pub struct NoDelay;
+
+impl DelayNs for NoDelay {
+    fn delay_ns(&mut self, _ns: u32) {}
+}
+
+/// The bus protocol of the parallel interface, selected through the `C86` pin of the ST7565
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Protocol {
+    /// 8080 bus: separate active low write and read strobes `/WR` and `/RD`
+    Intel8080,
+    /// 6800 bus: a direction pin `R/W` and an active high enable strobe `E`
+    Motorola6800,
+}
+
+/// The eight data pins `D0` to `D7` of the parallel interface
+pub struct DataBus<D0, D1, D2, D3, D4, D5, D6, D7> {
+    pins: (D0, D1, D2, D3, D4, D5, D6, D7),
+}
+
+fn set_pin<P: OutputPin>(pin: &mut P, high: bool) -> Result<(), DisplayError> {
+    pin.set_state(PinState::from(high))
+        .map_err(|_| DisplayError::BusWriteError)
+}
+
+fn read_pin<P: InputPin>(pin: &mut P) -> Result<bool, DisplayError> {
+    pin.is_high().map_err(|_| DisplayError::BusWriteError)
+}
+
+impl<D0, D1, D2, D3, D4, D5, D6, D7> DataBus<D0, D1, D2, D3, D4, D5, D6, D7>
+where
+    D0: OutputPin,
+    D1: OutputPin,
+    D2: OutputPin,
+    D3: OutputPin,
+    D4: OutputPin,
+    D5: OutputPin,
+    D6: OutputPin,
+    D7: OutputPin,
+{
+    /// Creates a data bus from its pins, starting with `D0`
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(d0: D0, d1: D1, d2: D2, d3: D3, d4: D4, d5: D5, d6: D6, d7: D7) -> Self {
+        Self {
+            pins: (d0, d1, d2, d3, d4, d5, d6, d7),
+        }
+    }
+
+    /// Releases the data pins
+    pub fn release(self) -> (D0, D1, D2, D3, D4, D5, D6, D7) {
+        self.pins
+    }
+
+    fn write(&mut self, byte: u8) -> Result<(), DisplayError> {
+        set_pin(&mut self.pins.0, byte & 0b00000001 != 0)?;
+        set_pin(&mut self.pins.1, byte & 0b00000010 != 0)?;
+        set_pin(&mut self.pins.2, byte & 0b00000100 != 0)?;
+        set_pin(&mut self.pins.3, byte & 0b00001000 != 0)?;
+        set_pin(&mut self.pins.4, byte & 0b00010000 != 0)?;
+        set_pin(&mut self.pins.5, byte & 0b00100000 != 0)?;
+        set_pin(&mut self.pins.6, byte & 0b01000000 != 0)?;
+        set_pin(&mut self.pins.7, byte & 0b10000000 != 0)
+    }
+}
+
+impl<D0, D1, D2, D3, D4, D5, D6, D7> DataBus<D0, D1, D2, D3, D4, D5, D6, D7>
+where
+    D0: OutputPin + InputPin,
+    D1: OutputPin + InputPin,
+    D2: OutputPin + InputPin,
+    D3: OutputPin + InputPin,
+    D4: OutputPin + InputPin,
+    D5: OutputPin + InputPin,
+    D6: OutputPin + InputPin,
+    D7: OutputPin + InputPin,
+{
+    /// Drives all pins high, so the display can pull them low
+    fn release_lines(&mut self) -> Result<(), DisplayError> {
+        self.write(0xFF)
+    }
+
+    fn read(&mut self) -> Result<u8, DisplayError> {
+        let bits = [
+            read_pin(&mut self.pins.0)?,
+            read_pin(&mut self.pins.1)?,
+            read_pin(&mut self.pins.2)?,
+            read_pin(&mut self.pins.3)?,
+            read_pin(&mut self.pins.4)?,
+            read_pin(&mut self.pins.5)?,
+            read_pin(&mut self.pins.6)?,
+            read_pin(&mut self.pins.7)?,
+        ];
+        Ok(bits
+            .iter()
+            .enumerate()
+            .fold(0, |byte, (index, &bit)| byte | ((bit as u8) << index)))
+    }
+}
+
+/// A bit-banged 8-bit parallel display interface.
+///
+/// `WR` and `RD` are the `/WR` and `/RD` pins on the 8080 bus, and the `R/W`
+/// and `E` pins on the 6800 bus.
+///
+/// `DELAY` holds the phases of the strobes, see [`with_delay()`](Self::with_delay).
+pub struct ParallelInterface<BUS, A0, WR, RD, CS, DELAY = NoDelay> {
+    bus: BUS,
+    a0: A0,
+    wr: WR,
+    rd: RD,
+    cs: CS,
+    protocol: Protocol,
+    delay: DELAY,
+}
+
+impl<BUS, A0, WR, RD, CS, DELAY> ParallelInterface<BUS, A0, WR, RD, CS, DELAY> {
+    /// The bus protocol of the interface
+    pub fn protocol(&self) -> Protocol {
+        self.protocol
+    }
+
+    /// Releases the pins, in the order they were given to the constructor
+    pub fn release(self) -> (BUS, A0, WR, RD, CS) {
+        (self.bus, self.a0, self.wr, self.rd, self.cs)
+    }
+
+    /// Holds each phase of the strobes for [`STROBE_PHASE_NS`] with the given delay,
+    /// to meet the bus timing of the display on fast microcontrollers.
+    pub fn with_delay<D: DelayNs>(self, delay: D) -> ParallelInterface<BUS, A0, WR, RD, CS, D> {
+        ParallelInterface {
+            bus: self.bus,
+            a0: self.a0,
+            wr: self.wr,
+            rd: self.rd,
+            cs: self.cs,
+            protocol: self.protocol,
+            delay,
+        }
+    }
+
+    /// Releases the delay given to [`with_delay()`](Self::with_delay)
+    pub fn release_delay(self) -> (ParallelInterface<BUS, A0, WR, RD, CS>, DELAY) {
+        let interface = ParallelInterface {
+            bus: self.bus,
+            a0: self.a0,
+            wr: self.wr,
+            rd: self.rd,
+            cs: self.cs,
+            protocol: self.protocol,
+            delay: NoDelay,
+        };
+        (interface, self.delay)
+    }
+}
+
+impl<D0, D1, D2, D3, D4, D5, D6, D7, A0, WR, RD, CS>
+    ParallelInterface<DataBus<D0, D1, D2, D3, D4, D5, D6, D7>, A0, WR, RD, CS>
+where
+    D0: OutputPin,
+    D1: OutputPin,
+    D2: OutputPin,
+    D3: OutputPin,
+    D4: OutputPin,
+    D5: OutputPin,
+    D6: OutputPin,
+    D7: OutputPin,
+    A0: OutputPin,
+    WR: OutputPin,
+    RD: OutputPin,
+    CS: OutputPin,
+{
+    /// Creates an interface for the 8080 bus, from the `/WR` and `/RD` pins
+    pub fn new_8080(
+        bus: DataBus<D0, D1, D2, D3, D4, D5, D6, D7>,
+        a0: A0,
+        wr: WR,
+        rd: RD,
+        cs: CS,
+    ) -> Self {
+        Self {
+            bus,
+            a0,
+            wr,
+            rd,
+            cs,
+            protocol: Protocol::Intel8080,
+            delay: NoDelay,
+        }
+    }
+
+    /// Creates an interface for the 6800 bus, from the `R/W` and `E` pins
+    pub fn new_6800(
+        bus: DataBus<D0, D1, D2, D3, D4, D5, D6, D7>,
+        a0: A0,
+        rw: WR,
+        e: RD,
+        cs: CS,
+    ) -> Self {
+        Self {
+            bus,
+            a0,
+            wr: rw,
+            rd: e,
+            cs,
+            protocol: Protocol::Motorola6800,
+            delay: NoDelay,
+        }
+    }
+}
+
+impl<D0, D1, D2, D3, D4, D5, D6, D7, A0, WR, RD, CS, DELAY>
+    ParallelInterface<DataBus<D0, D1, D2, D3, D4, D5, D6, D7>, A0, WR, RD, CS, DELAY>
+where
+    D0: OutputPin,
+    D1: OutputPin,
+    D2: OutputPin,
+    D3: OutputPin,
+    D4: OutputPin,
+    D5: OutputPin,
+    D6: OutputPin,
+    D7: OutputPin,
+    A0: OutputPin,
+    WR: OutputPin,
+    RD: OutputPin,
+    CS: OutputPin,
+    DELAY: DelayNs,
+{
+    /// Sets a strobe pin and holds the new level for one phase
+    fn strobe(pin: &mut impl OutputPin, high: bool, delay: &mut DELAY) -> Result<(), DisplayError> {
+        set_pin(pin, high)?;
+        delay.delay_ns(STROBE_PHASE_NS);
+        Ok(())
+    }
+
+    /// Selects the display and the register given by `a0`, with both strobes inactive
+    fn begin(&mut self, a0: bool) -> Result<(), DisplayError> {
+        match self.protocol {
+            Protocol::Intel8080 => {
+                set_pin(&mut self.wr, true)?;
+                set_pin(&mut self.rd, true)?;
+            }
+            Protocol::Motorola6800 => set_pin(&mut self.rd, false)?,
+        }
+        self.a0
+            .set_state(PinState::from(a0))
+            .map_err(|_| DisplayError::DCError)?;
+        self.cs.set_low().map_err(|_| DisplayError::CSError)
+    }
+
+    fn end(&mut self) -> Result<(), DisplayError> {
+        self.cs.set_high().map_err(|_| DisplayError::CSError)
+    }
+
+    fn write_byte(&mut self, byte: u8) -> Result<(), DisplayError> {
+        match self.protocol {
+            // Latched at the rising edge of /WR
+            Protocol::Intel8080 => {
+                self.bus.write(byte)?;
+                Self::strobe(&mut self.wr, false, &mut self.delay)?;
+                Self::strobe(&mut self.wr, true, &mut self.delay)
+            }
+            // Latched at the falling edge of E
+            Protocol::Motorola6800 => {
+                set_pin(&mut self.wr, false)?;
+                self.bus.write(byte)?;
+                Self::strobe(&mut self.rd, true, &mut self.delay)?;
+                Self::strobe(&mut self.rd, false, &mut self.delay)
+            }
+        }
+    }
+
+    fn write_bytes(&mut self, format: DataFormat<'_>) -> Result<(), DisplayError> {
+        match format {
+            DataFormat::U8(bytes) => {
+                for &byte in bytes {
+                    self.write_byte(byte)?;
+                }
+            }
+            DataFormat::U8Iter(bytes) => {
+                for byte in bytes {
+                    self.write_byte(byte)?;
+                }
+            }
+            _ => return Err(DisplayError::DataFormatNotImplemented),
+        }
+        Ok(())
+    }
+
+    fn write(&mut self, a0: bool, format: DataFormat<'_>) -> Result<(), DisplayError> {
+        self.begin(a0)?;
+        let result = self.write_bytes(format);
+        // Always deselect the display, even after an error
+        let end = self.end();
+        result.and(end)
+    }
+}
+
+impl<D0, D1, D2, D3, D4, D5, D6, D7, A0, WR, RD, CS, DELAY>
+    ParallelInterface<DataBus<D0, D1, D2, D3, D4, D5, D6, D7>, A0, WR, RD, CS, DELAY>
+where
+    D0: OutputPin + InputPin,
+    D1: OutputPin + InputPin,
+    D2: OutputPin + InputPin,
+    D3: OutputPin + InputPin,
+    D4: OutputPin + InputPin,
+    D5: OutputPin + InputPin,
+    D6: OutputPin + InputPin,
+    D7: OutputPin + InputPin,
+    A0: OutputPin,
+    WR: OutputPin,
+    RD: OutputPin,
+    CS: OutputPin,
+    DELAY: DelayNs,
+{
+    fn read_byte(&mut self) -> Result<u8, DisplayError> {
+        match self.protocol {
+            // Driven while /RD is low
+            Protocol::Intel8080 => {
+                Self::strobe(&mut self.rd, false, &mut self.delay)?;
+                let byte = self.bus.read();
+                Self::strobe(&mut self.rd, true, &mut self.delay)?;
+                byte
+            }
+            // Driven while E is high
+            Protocol::Motorola6800 => {
+                set_pin(&mut self.wr, true)?;
+                Self::strobe(&mut self.rd, true, &mut self.delay)?;
+                let byte = self.bus.read();
+                Self::strobe(&mut self.rd, false, &mut self.delay)?;
+                byte
+            }
+        }
+    }
+
+    fn read(&mut self, a0: bool, buf: &mut [u8]) -> Result<(), DisplayError> {
+        self.bus.release_lines()?;
+        self.begin(a0)?;
+        let result = buf.iter_mut().try_for_each(|byte| {
+            *byte = self.read_byte()?;
+            Ok(())
+        });
+        // Always deselect the display, even after an error
+        let end = self.end();
+        result.and(end)
+    }
+}
+
+impl<D0, D1, D2, D3, D4, D5, D6, D7, A0, WR, RD, CS, DELAY> WriteOnlyDataCommand
+    for ParallelInterface<DataBus<D0, D1, D2, D3, D4, D5, D6, D7>, A0, WR, RD, CS, DELAY>
+where
+    D0: OutputPin,
+    D1: OutputPin,
+    D2: OutputPin,
+    D3: OutputPin,
+    D4: OutputPin,
+    D5: OutputPin,
+    D6: OutputPin,
+    D7: OutputPin,
+    A0: OutputPin,
+    WR: OutputPin,
+    RD: OutputPin,
+    CS: OutputPin,
+    DELAY: DelayNs,
+{
+    fn send_commands(&mut self, cmd: DataFormat<'_>) -> Result<(), DisplayError> {
+        self.write(false, cmd)
+    }
+
+    fn send_data(&mut self, buf: DataFormat<'_>) -> Result<(), DisplayError> {
+        self.write(true, buf)
+    }
+}
+
+impl<D0, D1, D2, D3, D4, D5, D6, D7, A0, WR, RD, CS, DELAY> ReadableDataCommand
+    for ParallelInterface<DataBus<D0, D1, D2, D3, D4, D5, D6, D7>, A0, WR, RD, CS, DELAY>
+where
+    D0: OutputPin + InputPin,
+    D1: OutputPin + InputPin,
+    D2: OutputPin + InputPin,
+    D3: OutputPin + InputPin,
+    D4: OutputPin + InputPin,
+    D5: OutputPin + InputPin,
+    D6: OutputPin + InputPin,
+    D7: OutputPin + InputPin,
+    A0: OutputPin,
+    WR: OutputPin,
+    RD: OutputPin,
+    CS: OutputPin,
+    DELAY: DelayNs,
+{
+    fn read_data(&mut self, buf: &mut [u8]) -> Result<(), DisplayError> {
+        self.read(true, buf)
+    }
+
+    fn read_status(&mut self) -> Result<u8, DisplayError> {
+        let mut status = [0];
+        self.read(false, &mut status)?;
+        Ok(status[0])
+    }
+}
@@ -0,0 +1,137 @@
+//! A display interface for an [`SpiDevice`] and an `A0` pin.
+//!
+//! ```rust
+//! # use st7565::{displays::DOGM132W5, interface::spi::SpiInterface, ST7565};
+//! # struct Pin;
+//! # impl embedded_hal::digital::ErrorType for Pin { type Error = core::convert::Infallible; }
+//! # impl embedded_hal::digital::OutputPin for Pin {
+//! #     fn set_low(&mut self) -> Result<(), Self::Error> { Ok(()) }
+//! #     fn set_high(&mut self) -> Result<(), Self::Error> { Ok(()) }
+//! # }
+//! # struct Spi;
+//! # impl embedded_hal::spi::ErrorType for Spi { type Error = core::convert::Infallible; }
+//! # impl embedded_hal::spi::SpiDevice for Spi {
+//! #     fn transaction(&mut self, _: &mut [embedded_hal::spi::Operation<'_, u8>]) -> Result<(), Self::Error> { Ok(()) }
+//! # }
+//! # let (spi_device, disp_a0) = (Spi, Pin);
+//! let interface = SpiInterface::new(spi_device, disp_a0);
+//! let disp = ST7565::new(interface, DOGM132W5).into_raw_mode();
+//! ```
+//!
+//! Every transfer of a byte slice ([`DataFormat::U8`]) becomes a single SPI transaction.
+//! The bytes of an iterator ([`DataFormat::U8Iter`]) are collected in chunks of up to
+//! 32 bytes, and every chunk is sent in its own transaction.
+//!
+//! The driver sends consecutive commands, like the page and column address before a
+//! data transfer, in a single transfer. Commands and data can't share a transaction,
+//! as the `A0` pin has to change between them.
+
+#[cfg(feature = "async")]
+use display_interface::AsyncWriteOnlyDataCommand;
+use display_interface::{DataFormat, DisplayError, WriteOnlyDataCommand};
+use embedded_hal::{digital::OutputPin, spi::SpiDevice};
+
+use super::next_chunk;
+
+/// A display interface for an [`SpiDevice`] and an `A0` pin.
+///
+/// Also implements [`display_interface::AsyncWriteOnlyDataCommand`] for async SPI devices,
+/// if the `async` feature is enabled.
+///
+/// Only [`DataFormat::U8`] and [`DataFormat::U8Iter`] are supported.
+pub struct SpiInterface<SPI, A0> {
+    spi: SPI,
+    a0: A0,
+}
+
+impl<SPI, A0> SpiInterface<SPI, A0> {
+    /// Creates an interface from an SPI device and the `A0` pin
+    pub fn new(spi: SPI, a0: A0) -> Self {
+        Self { spi, a0 }
+    }
+
+    /// Releases the SPI device and the `A0` pin
+    pub fn release(self) -> (SPI, A0) {
+        (self.spi, self.a0)
+    }
+}
+
+impl<SPI, A0> SpiInterface<SPI, A0>
+where
+    SPI: SpiDevice,
+    A0: OutputPin,
+{
+    fn write(&mut self, a0: bool, format: DataFormat<'_>) -> Result<(), DisplayError> {
+        self.a0
+            .set_state(a0.into())
+            .map_err(|_| DisplayError::DCError)?;
+
+        let err = |_| DisplayError::BusWriteError;
+        match format {
+            DataFormat::U8(bytes) => self.spi.write(bytes).map_err(err),
+            DataFormat::U8Iter(bytes) => loop {
+                let (buffer, len) = next_chunk(bytes);
+                if len == 0 {
+                    return Ok(());
+                }
+                self.spi.write(&buffer[..len]).map_err(err)?;
+            },
+            _ => Err(DisplayError::DataFormatNotImplemented),
+        }
+    }
+}
+
+impl<SPI, A0> WriteOnlyDataCommand for SpiInterface<SPI, A0>
+where
+    SPI: SpiDevice,
+    A0: OutputPin,
+{
+    fn send_commands(&mut self, cmd: DataFormat<'_>) -> Result<(), DisplayError> {
+        self.write(false, cmd)
+    }
+
+    fn send_data(&mut self, buf: DataFormat<'_>) -> Result<(), DisplayError> {
+        self.write(true, buf)
+    }
+}
+
+#[cfg(feature = "async")]
+impl<SPI, A0> SpiInterface<SPI, A0>
+where
+    SPI: embedded_hal_async::spi::SpiDevice,
+    A0: OutputPin,
+{
+    async fn write_async(&mut self, a0: bool, format: DataFormat<'_>) -> Result<(), DisplayError> {
+        self.a0
+            .set_state(a0.into())
+            .map_err(|_| DisplayError::DCError)?;
+
+        let err = |_| DisplayError::BusWriteError;
+        match format {
+            DataFormat::U8(bytes) => self.spi.write(bytes).await.map_err(err),
+            DataFormat::U8Iter(bytes) => loop {
+                let (buffer, len) = next_chunk(bytes);
+                if len == 0 {
+                    return Ok(());
+                }
+                self.spi.write(&buffer[..len]).await.map_err(err)?;
+            },
+            _ => Err(DisplayError::DataFormatNotImplemented),
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+impl<SPI, A0> AsyncWriteOnlyDataCommand for SpiInterface<SPI, A0>
+where
+    SPI: embedded_hal_async::spi::SpiDevice,
+    A0: OutputPin,
+{
+    async fn send_commands(&mut self, cmd: DataFormat<'_>) -> Result<(), DisplayError> {
+        self.write_async(false, cmd).await
+    }
+
+    async fn send_data(&mut self, buf: DataFormat<'_>) -> Result<(), DisplayError> {
+        self.write_async(true, buf).await
+    }
+}
@@ -0,0 +1,164 @@
+//! A display interface for the 3-wire 9-bit SPI mode, which does not need an `A0` pin.
+//!
+//! ```rust
+//! # use st7565::{displays::DOGS102_6, interface::spi_9bit::Spi9BitInterface, ST7565};
+//! # struct Spi;
+//! # impl embedded_hal::spi::ErrorType for Spi { type Error = core::convert::Infallible; }
+//! # impl embedded_hal::spi::SpiDevice for Spi {
+//! #     fn transaction(&mut self, _: &mut [embedded_hal::spi::Operation<'_, u8>]) -> Result<(), Self::Error> { Ok(()) }
+//! # }
+//! # let spi_device = Spi;
+//! let interface = Spi9BitInterface::new(spi_device);
+//! let disp = ST7565::new(interface, DOGS102_6).into_raw_mode();
+//! ```
+//!
+//! Every byte is sent as a 9-bit frame, with the `A0` bit in front of the byte.
+//! As most SPI peripherals only transfer whole bytes, the frames get packed into
+//! 8-bit words. Transfers are padded with [`Nop`](crate::command::Command::Nop)
+//! commands to a multiple of 8 frames, so every transfer ends on a frame boundary.
+
+#[cfg(feature = "async")]
+use display_interface::AsyncWriteOnlyDataCommand;
+use display_interface::{DataFormat, DisplayError, WriteOnlyDataCommand};
+use embedded_hal::spi::SpiDevice;
+
+use super::{next_chunk, CHUNK_SIZE};
+use crate::command::Command;
+
+/// The number of bytes that [`CHUNK_SIZE`] frames occupy
+const PACKED_SIZE: usize = CHUNK_SIZE * 9 / 8;
+
+// Only the last chunk of a transfer may need padding
+const _: () = assert!(CHUNK_SIZE % 8 == 0);
+
+/// Packs bytes into 9-bit frames with the given `A0` bit.
+///
+/// Returns the buffer and the number of bytes used.
+fn pack(a0: bool, bytes: &[u8]) -> ([u8; PACKED_SIZE], usize) {
+    let nop = Command::Nop.encode().as_bytes()[0];
+    let padding = (8 - bytes.len() % 8) % 8;
+    let frames = bytes
+        .iter()
+        .map(|&byte| (u16::from(a0) << 8) | u16::from(byte))
+        .chain(core::iter::repeat(u16::from(nop)).take(padding));
+
+    let mut buffer = [0; PACKED_SIZE];
+    let mut bit = 0;
+    for frame in frames {
+        for shift in (0..9).rev() {
+            if frame & (1 << shift) != 0 {
+                buffer[bit / 8] |= 0x80 >> (bit % 8);
+            }
+            bit += 1;
+        }
+    }
+    (buffer, bit / 8)
+}
+
+/// A display interface for the 3-wire 9-bit SPI mode of an [`SpiDevice`].
+///
+/// Also implements [`display_interface::AsyncWriteOnlyDataCommand`] for async SPI devices,
+/// if the `async` feature is enabled.
+///
+/// Only [`DataFormat::U8`] and [`DataFormat::U8Iter`] are supported.
+pub struct Spi9BitInterface<SPI> {
+    spi: SPI,
+}
+
+impl<SPI> Spi9BitInterface<SPI> {
+    /// Creates an interface from an SPI device
+    pub fn new(spi: SPI) -> Self {
+        Self { spi }
+    }
+
+    /// Releases the SPI device
+    pub fn release(self) -> SPI {
+        self.spi
+    }
+}
+
+impl<SPI> Spi9BitInterface<SPI>
+where
+    SPI: SpiDevice,
+{
+    fn write(&mut self, a0: bool, format: DataFormat<'_>) -> Result<(), DisplayError> {
+        let mut write = |bytes: &[u8]| {
+            let (buffer, len) = pack(a0, bytes);
+            self.spi
+                .write(&buffer[..len])
+                .map_err(|_| DisplayError::BusWriteError)
+        };
+
+        match format {
+            DataFormat::U8(bytes) => bytes.chunks(CHUNK_SIZE).try_for_each(write),
+            DataFormat::U8Iter(bytes) => loop {
+                let (buffer, len) = next_chunk(bytes);
+                if len == 0 {
+                    return Ok(());
+                }
+                write(&buffer[..len])?;
+            },
+            _ => Err(DisplayError::DataFormatNotImplemented),
+        }
+    }
+}
+
+impl<SPI> WriteOnlyDataCommand for Spi9BitInterface<SPI>
+where
+    SPI: SpiDevice,
+{
+    fn send_commands(&mut self, cmd: DataFormat<'_>) -> Result<(), DisplayError> {
+        self.write(false, cmd)
+    }
+
+    fn send_data(&mut self, buf: DataFormat<'_>) -> Result<(), DisplayError> {
+        self.write(true, buf)
+    }
+}
+
+#[cfg(feature = "async")]
+impl<SPI> Spi9BitInterface<SPI>
+where
+    SPI: embedded_hal_async::spi::SpiDevice,
+{
+    async fn write_chunk_async(&mut self, a0: bool, bytes: &[u8]) -> Result<(), DisplayError> {
+        let (buffer, len) = pack(a0, bytes);
+        self.spi
+            .write(&buffer[..len])
+            .await
+            .map_err(|_| DisplayError::BusWriteError)
+    }
+
+    async fn write_async(&mut self, a0: bool, format: DataFormat<'_>) -> Result<(), DisplayError> {
+        match format {
+            DataFormat::U8(bytes) => {
+                for chunk in bytes.chunks(CHUNK_SIZE) {
+                    self.write_chunk_async(a0, chunk).await?;
+                }
+                Ok(())
+            }
+            DataFormat::U8Iter(bytes) => loop {
+                let (buffer, len) = next_chunk(bytes);
+                if len == 0 {
+                    return Ok(());
+                }
+                self.write_chunk_async(a0, &buffer[..len]).await?;
+            },
+            _ => Err(DisplayError::DataFormatNotImplemented),
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+impl<SPI> AsyncWriteOnlyDataCommand for Spi9BitInterface<SPI>
+where
+    SPI: embedded_hal_async::spi::SpiDevice,
+{
+    async fn send_commands(&mut self, cmd: DataFormat<'_>) -> Result<(), DisplayError> {
+        self.write_async(false, cmd).await
+    }
+
+    async fn send_data(&mut self, buf: DataFormat<'_>) -> Result<(), DisplayError> {
+        self.write_async(true, buf).await
+    }
+}
@@ -1,6 +1,8 @@
 #![no_std]
 #![deny(missing_docs)]
-#![forbid(unsafe_code)]
+#![cfg_attr(not(test), forbid(unsafe_code))]
+// The tests need a hand-written no-op waker
+#![cfg_attr(test, deny(unsafe_code))]
 #![doc = include_str!("../README.md")]
 #![doc(issue_tracker_base_url = "https://github.com/Finomnis/st7565/issues")]
 // Building tests on target with defmt_tests requires `no_main`
@@ -9,22 +11,37 @@
 #[cfg(test)]
 mod tests;
 
-mod command;
 mod display_specs;
 mod driver;
 mod error;
 
+pub mod command;
 pub mod displays;
+#[cfg(feature = "emulator")]
+pub mod emulator;
+pub mod interface;
+#[cfg(any(test, feature = "mock"))]
+pub mod mock;
 pub mod types;
 
 pub use display_specs::DisplaySpecs;
+#[cfg(feature = "async")]
+pub use driver::AsyncInterface;
 pub use driver::GraphicsPageBuffer;
+pub use driver::GrayscalePageBuffer;
+pub use driver::PageBand;
+pub use driver::PageBandBuffer;
+pub use driver::ShadowBuffer;
 pub use driver::ST7565;
-pub use error::Error;
+pub use error::{Error, FlushError, HealthCheckError};
 
 /// Operating modes of the driver
 pub mod modes {
+    pub use crate::driver::mode_direct_graphics::DirectGraphicsMode;
     pub use crate::driver::mode_graphics::GraphicsMode;
+    pub use crate::driver::mode_grayscale::GrayscaleMode;
     pub use crate::driver::mode_initial::InitialMode;
+    pub use crate::driver::mode_page_band::PageBandMode;
     pub use crate::driver::mode_raw::RawMode;
+    pub use crate::driver::mode_terminal::TerminalMode;
 }
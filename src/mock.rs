@@ -0,0 +1,340 @@
+//! A mock display interface for unit tests.
+//!
+//! [`DisplayMock`] implements [`WriteOnlyDataCommand`] and can therefore be used as the
+//! display interface of the driver. It either compares all traffic against a list of
+//! [`ExpectedAction`]s, or captures it into a [`Recording`].
+//!
+//! ```rust
+//! # use st7565::{displays::DOGM132W5, mock::{DisplayMock, ExpectedAction::*}, ST7565};
+//! let expected = [Command(&[0b10101111])];
+//!
+//! DisplayMock::with_expect(&expected, |disp_mock| {
+//!     let mut disp = ST7565::new(disp_mock, DOGM132W5).into_raw_mode();
+//!     disp.set_display_on(true).unwrap();
+//! });
+//! ```
+//!
+//! All [`DataFormat`]s are supported; 16 bit values are converted to bytes
+//! in their respective byte order.
+
+use display_interface::{DataFormat, DisplayError, WriteOnlyDataCommand};
+
+/// A transfer to the display.
+///
+/// Used both to describe expected transfers and to report recorded ones.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ExpectedAction<'a> {
+    /// Command bytes
+    Command(&'a [u8]),
+    /// Data bytes
+    Data(&'a [u8]),
+}
+use ExpectedAction::*;
+
+/// Captures the traffic of a [`DisplayMock`], up to `CAPACITY` bytes.
+///
+/// Transfers without any bytes are not recorded.
+pub struct Recording<const CAPACITY: usize> {
+    bytes: [u8; CAPACITY],
+    action_starts: [Option<bool>; CAPACITY],
+    len: usize,
+}
+
+impl<const CAPACITY: usize> Recording<CAPACITY> {
+    /// Creates an empty recording.
+    pub const fn new() -> Self {
+        Self {
+            bytes: [0; CAPACITY],
+            action_starts: [None; CAPACITY],
+            len: 0,
+        }
+    }
+
+    /// The recorded transfers, in order.
+    pub fn actions(&self) -> impl Iterator<Item = ExpectedAction<'_>> + '_ {
+        let starts = &self.action_starts[..self.len];
+
+        starts
+            .iter()
+            .enumerate()
+            .filter_map(move |(start, is_data)| {
+                let is_data = (*is_data)?;
+                let end = starts[start + 1..]
+                    .iter()
+                    .position(Option::is_some)
+                    .map_or(self.len, |pos| start + 1 + pos);
+                let bytes = &self.bytes[start..end];
+                Some(if is_data { Data(bytes) } else { Command(bytes) })
+            })
+    }
+
+    /// Removes all recorded transfers.
+    pub fn clear(&mut self) {
+        self.action_starts = [None; CAPACITY];
+        self.len = 0;
+    }
+
+    fn record(&mut self, is_data: bool, data: DataFormat<'_>) -> Result<(), DisplayError> {
+        let start = self.len;
+        let mut result = Ok(());
+
+        for_each_byte(data, |byte| {
+            if result.is_err() {
+                return;
+            }
+            if self.len >= CAPACITY {
+                result = Err(());
+                return;
+            }
+            self.bytes[self.len] = byte;
+            self.action_starts[self.len] = None;
+            self.len += 1;
+        })?;
+
+        assert!(
+            result.is_ok(),
+            "Recording capacity of {} bytes exceeded!",
+            CAPACITY
+        );
+
+        if start < self.len {
+            self.action_starts[start] = Some(is_data);
+        }
+
+        Ok(())
+    }
+}
+
+impl<const CAPACITY: usize> Default for Recording<CAPACITY> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+trait Recorder {
+    fn record(&mut self, is_data: bool, data: DataFormat<'_>) -> Result<(), DisplayError>;
+}
+
+impl<const CAPACITY: usize> Recorder for Recording<CAPACITY> {
+    fn record(&mut self, is_data: bool, data: DataFormat<'_>) -> Result<(), DisplayError> {
+        Recording::record(self, is_data, data)
+    }
+}
+
+enum Mode<'a, 'b> {
+    Expect(&'a [ExpectedAction<'b>]),
+    Record(&'a mut dyn Recorder),
+}
+
+/// A mock display interface.
+///
+/// In expectation mode, every transfer gets compared against the next [`ExpectedAction`];
+/// deviations cause a panic. In recording mode, all transfers are stored in a [`Recording`].
+///
+/// Both modes can additionally inject a [`DisplayError`] at a chosen step, to test
+/// error handling.
+pub struct DisplayMock<'a, 'b> {
+    mode: Mode<'a, 'b>,
+    step: usize,
+    failure: Option<(usize, DisplayError)>,
+}
+
+impl<'a, 'b> DisplayMock<'a, 'b> {
+    /// Creates a mock that expects the given actions, in order.
+    ///
+    /// Call [`verify()`](Self::verify) at the end of the test to make sure
+    /// that all expected actions happened.
+    pub fn new(expected: &'a [ExpectedAction<'b>]) -> Self {
+        Self {
+            mode: Mode::Expect(expected),
+            step: 0,
+            failure: None,
+        }
+    }
+
+    /// Creates a mock that records all traffic instead of checking it.
+    pub fn recording<const CAPACITY: usize>(recording: &'a mut Recording<CAPACITY>) -> Self {
+        Self {
+            mode: Mode::Record(recording),
+            step: 0,
+            failure: None,
+        }
+    }
+
+    /// Makes the given transfer fail.
+    ///
+    /// `step` is the zero-based index of the transfer, counting both commands and data.
+    /// The failing transfer is still checked or recorded.
+    pub fn fail_at(mut self, step: usize, error: DisplayError) -> Self {
+        self.failure = Some((step, error));
+        self
+    }
+
+    /// Runs the given closure with a mock that expects the given actions,
+    /// and verifies that all of them happened.
+    pub fn with_expect<T>(
+        expected: &'a [ExpectedAction<'b>],
+        f: impl FnOnce(&mut DisplayMock<'a, 'b>) -> T,
+    ) -> T {
+        let mut mock = DisplayMock::new(expected);
+
+        let result = f(&mut mock);
+
+        mock.verify();
+
+        result
+    }
+
+    /// Asserts that all expected actions happened.
+    pub fn verify(&self) {
+        if let Mode::Expect(expected_actions) = &self.mode {
+            assert!(
+                expected_actions.is_empty(),
+                "Actions were expected, but did not happen: {:?}",
+                expected_actions
+            );
+        }
+    }
+
+    fn process(&mut self, is_data: bool, data: DataFormat<'_>) -> Result<(), DisplayError> {
+        let step = self.step;
+        self.step += 1;
+
+        match &mut self.mode {
+            Mode::Expect(expected_actions) => {
+                let (expected, leftover) = expected_actions
+                    .split_first()
+                    .expect("No action was expected!");
+                *expected_actions = leftover;
+
+                check_action(expected, is_data, data)?;
+            }
+            Mode::Record(recording) => recording.record(is_data, data)?,
+        }
+
+        match &self.failure {
+            Some((failing_step, error)) if *failing_step == step => Err(error.clone()),
+            _ => Ok(()),
+        }
+    }
+}
+
+fn check_action(
+    expected: &ExpectedAction<'_>,
+    is_data: bool,
+    data: DataFormat<'_>,
+) -> Result<(), DisplayError> {
+    let (kind, expected_bytes) = match *expected {
+        Command(bytes) => (false, bytes),
+        Data(bytes) => (true, bytes),
+    };
+
+    if let DataFormat::U8(actual) = data {
+        let actual = if is_data {
+            Data(actual)
+        } else {
+            Command(actual)
+        };
+        assert_eq!(expected, &actual, "Unexpected action received!");
+        return Ok(());
+    }
+
+    assert_eq!(
+        kind, is_data,
+        "Unexpected action received! Expected {:?}",
+        expected
+    );
+
+    let mut position = 0;
+    for_each_byte(data, |byte| {
+        assert_eq!(
+            expected_bytes.get(position),
+            Some(&byte),
+            "Unexpected byte at position {}! Expected {:?}",
+            position,
+            expected
+        );
+        position += 1;
+    })?;
+    assert_eq!(
+        position,
+        expected_bytes.len(),
+        "Too few bytes received! Expected {:?}",
+        expected
+    );
+
+    Ok(())
+}
+
+fn for_each_byte(data: DataFormat<'_>, mut f: impl FnMut(u8)) -> Result<(), DisplayError> {
+    match data {
+        DataFormat::U8(bytes) => bytes.iter().copied().for_each(f),
+        DataFormat::U16(values) => values.iter().flat_map(|v| v.to_ne_bytes()).for_each(f),
+        DataFormat::U16BE(values) => values.iter().flat_map(|v| v.to_be_bytes()).for_each(f),
+        DataFormat::U16LE(values) => values.iter().flat_map(|v| v.to_le_bytes()).for_each(f),
+        DataFormat::U8Iter(bytes) => {
+            for byte in bytes {
+                f(byte);
+            }
+        }
+        DataFormat::U16BEIter(values) => {
+            for value in values {
+                let [first, second] = value.to_be_bytes();
+                f(first);
+                f(second);
+            }
+        }
+        DataFormat::U16LEIter(values) => {
+            for value in values {
+                let [first, second] = value.to_le_bytes();
+                f(first);
+                f(second);
+            }
+        }
+        _ => return Err(DisplayError::DataFormatNotImplemented),
+    }
+
+    Ok(())
+}
+
+impl WriteOnlyDataCommand for DisplayMock<'_, '_> {
+    fn send_commands(&mut self, cmd: DataFormat<'_>) -> Result<(), DisplayError> {
+        self.process(false, cmd)
+    }
+
+    fn send_data(&mut self, buf: DataFormat<'_>) -> Result<(), DisplayError> {
+        self.process(true, buf)
+    }
+}
+
+impl WriteOnlyDataCommand for &mut DisplayMock<'_, '_> {
+    fn send_commands(&mut self, cmd: DataFormat<'_>) -> Result<(), DisplayError> {
+        self.process(false, cmd)
+    }
+
+    fn send_data(&mut self, buf: DataFormat<'_>) -> Result<(), DisplayError> {
+        self.process(true, buf)
+    }
+}
+
+#[cfg(feature = "async")]
+impl display_interface::AsyncWriteOnlyDataCommand for DisplayMock<'_, '_> {
+    async fn send_commands(&mut self, cmd: DataFormat<'_>) -> Result<(), DisplayError> {
+        self.process(false, cmd)
+    }
+
+    async fn send_data(&mut self, buf: DataFormat<'_>) -> Result<(), DisplayError> {
+        self.process(true, buf)
+    }
+}
+
+#[cfg(feature = "async")]
+impl display_interface::AsyncWriteOnlyDataCommand for &mut DisplayMock<'_, '_> {
+    async fn send_commands(&mut self, cmd: DataFormat<'_>) -> Result<(), DisplayError> {
+        self.process(false, cmd)
+    }
+
+    async fn send_data(&mut self, buf: DataFormat<'_>) -> Result<(), DisplayError> {
+        self.process(true, buf)
+    }
+}
@@ -0,0 +1,31 @@
+use core::{
+    future::Future,
+    pin::pin,
+    task::{Context, Poll, RawWaker, RawWakerVTable, Waker},
+};
+
+/// A waker that does nothing, as `Waker::noop()` requires a newer Rust than the MSRV
+#[allow(unsafe_code)]
+fn noop_waker() -> Waker {
+    const VTABLE: RawWakerVTable = RawWakerVTable::new(|_| RAW, |_| {}, |_| {}, |_| {});
+    const RAW: RawWaker = RawWaker::new(core::ptr::null(), &VTABLE);
+
+    // SAFETY: None of the vtable functions use the data pointer
+    unsafe { Waker::from_raw(RAW) }
+}
+
+/// Polls a future to completion.
+///
+/// Only suitable for futures that never wait on anything, like the ones
+/// produced by the mocks of these tests.
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let waker = noop_waker();
+    let mut context = Context::from_waker(&waker);
+
+    loop {
+        if let Poll::Ready(result) = future.as_mut().poll(&mut context) {
+            return result;
+        }
+    }
+}
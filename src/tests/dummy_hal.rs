@@ -0,0 +1,30 @@
+use core::convert::Infallible;
+
+/// A reset pin that does nothing.
+pub struct DummyPin;
+
+impl embedded_hal::digital::ErrorType for DummyPin {
+    type Error = Infallible;
+}
+
+impl embedded_hal::digital::OutputPin for DummyPin {
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+/// A delay that returns immediately.
+pub struct DummyDelay;
+
+impl embedded_hal::delay::DelayNs for DummyDelay {
+    fn delay_ns(&mut self, _ns: u32) {}
+}
+
+#[cfg(feature = "async")]
+impl embedded_hal_async::delay::DelayNs for DummyDelay {
+    async fn delay_ns(&mut self, _ns: u32) {}
+}
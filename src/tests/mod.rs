@@ -1,14 +1,18 @@
 mod arch_dependent;
-mod display_mock;
+#[cfg(feature = "async")]
+mod block_on;
+mod dummy_hal;
+#[cfg(feature = "emulator")]
+mod parallel_bus;
 
 // defmt-test 0.3.0 has the limitation that this `#[tests]` attribute can only be used
 // once within a crate. the module can be in any file but there can only be at most
 // one `#[tests]` module in this library crate
 #[arch_dependent::tests]
 mod unit_tests {
-    use crate::{displays::DOGM132W5, GraphicsPageBuffer, ST7565};
+    use crate::{displays::DOGM132W5, GraphicsPageBuffer, ShadowBuffer, ST7565};
 
-    use super::display_mock::{DisplayMock, ExpectedAction::*};
+    use crate::mock::{DisplayMock, ExpectedAction::*};
 
     #[test]
     fn commands() {
@@ -17,12 +21,24 @@ mod unit_tests {
                 Command::{self, *},
                 SendSt7565Command,
             },
-            types::{BoosterRatio, PowerControlMode, StaticIndicatorMode},
+            types::{
+                BoosterRatio, Controller, PowerControlMode, StaticIndicatorMode,
+                TemperatureCompensation,
+            },
         };
         fn check_command(cmd: Command, result: &[u8]) {
-            DisplayMock::with_expect(&[Command(result)], |mut disp| {
-                disp.send_command(cmd).unwrap()
+            let controller = match cmd {
+                AdvancedProgramControl { .. } => Controller::Uc1701,
+                OscillatorOn | GrayLevelSet { .. } => Controller::St7571,
+                _ => Controller::St7565,
+            };
+            DisplayMock::with_expect(&[Command(result)], |disp| {
+                disp.send_command(controller, cmd).unwrap()
             });
+
+            let (decoded, len) = Command::decode(result).unwrap();
+            assert_eq!(len, result.len());
+            assert_eq!(decoded.encode().as_bytes(), result);
         }
 
         check_command(DisplayOnOff { on: true }, &[0b10101111]);
@@ -75,7 +91,9 @@ mod unit_tests {
         check_command(DisplayAllPoints { on: false }, &[0b10100100]);
         check_command(LcdBiasSet { bias_mode_1: true }, &[0b10100011]);
         check_command(LcdBiasSet { bias_mode_1: false }, &[0b10100010]);
-        //check_command(Reset, &[0b11100010]);
+        check_command(ReadModifyWrite, &[0b11100000]);
+        check_command(End, &[0b11101110]);
+        check_command(Reset, &[0b11100010]);
         check_command(CommonOutputModeSelect { reverse: true }, &[0b11001000]);
         check_command(CommonOutputModeSelect { reverse: false }, &[0b11000000]);
         check_command(
@@ -187,7 +205,142 @@ mod unit_tests {
             },
             &[0b11111000, 0b00000011],
         );
-        //check_command(NOP, &[0b11100011]);
+        check_command(
+            AdvancedProgramControl {
+                temperature_compensation: TemperatureCompensation::High,
+                column_wrap: false,
+                page_wrap: false,
+            },
+            &[0b11111010, 0b10010000],
+        );
+        check_command(
+            AdvancedProgramControl {
+                temperature_compensation: TemperatureCompensation::Low,
+                column_wrap: true,
+                page_wrap: true,
+            },
+            &[0b11111010, 0b00010011],
+        );
+        check_command(OscillatorOn, &[0b10101011]);
+        check_command(
+            GrayLevelSet {
+                register: 0b101,
+                value: 0x5A,
+            },
+            &[0b10001101, 0x5A],
+        );
+        check_command(Nop, &[0b11100011]);
+    }
+
+    #[test]
+    fn command_decoder() {
+        use crate::{
+            command::{Command, DecodeError, Decoder},
+            types::Controller,
+        };
+
+        // Everything that decodes must encode back to the same bytes
+        for first in 0..=u8::MAX {
+            for second in 0..=u8::MAX {
+                let bytes = [first, second];
+                if let Ok((command, len)) = Command::decode(&bytes) {
+                    assert_eq!(command.encode().as_bytes(), &bytes[..len]);
+                    assert_eq!(
+                        Command::decode(command.encode().as_bytes()),
+                        Ok((command, len))
+                    );
+                }
+            }
+        }
+
+        let bytes = [0b10101111, 0b00010010, 0b00000011, 0b11100011, 0b10000001];
+        let mut decoder = Decoder::new(&bytes);
+        assert_eq!(decoder.next(), Some(Ok(Command::DisplayOnOff { on: true })));
+        assert_eq!(
+            decoder.next(),
+            Some(Ok(Command::ColumnAddressSet {
+                address: 0b00100011
+            }))
+        );
+        assert_eq!(decoder.next(), Some(Ok(Command::Nop)));
+        assert_eq!(
+            decoder.next(),
+            Some(Err(DecodeError::Truncated {
+                command: 0b10000001
+            }))
+        );
+        assert_eq!(decoder.next(), None);
+
+        assert_eq!(
+            Command::decode(&[0b11110000]),
+            Err(DecodeError::UnknownCommand { byte: 0b11110000 })
+        );
+        assert_eq!(
+            Command::decode(&[0b11111000, 0b00000010]),
+            Err(DecodeError::InvalidArgument {
+                command: 0b11111000,
+                argument: 0b00000010
+            })
+        );
+        assert_eq!(Command::decode(&[]), Err(DecodeError::Empty));
+
+        // Decoding for a controller is the inverse of encoding for it
+        for controller in [
+            Controller::St7565,
+            Controller::St7567,
+            Controller::Uc1701,
+            Controller::S6b1713,
+            Controller::St7571,
+        ] {
+            for first in 0..=u8::MAX {
+                for second in 0..=u8::MAX {
+                    let bytes = [first, second, 0];
+                    if let Ok((command, len)) = Command::decode_for(controller, &bytes) {
+                        let encoded = command.encode_for(controller).unwrap();
+                        assert_eq!(encoded.as_bytes(), &bytes[..len]);
+                    }
+                }
+            }
+        }
+
+        // The ST7571 start line takes a second byte
+        for address in 0..128 {
+            let command = Command::DisplayStartLineSet { address };
+            let encoded = command.encode_for(Controller::St7571).unwrap();
+            assert_eq!(
+                Command::decode_for(Controller::St7571, encoded.as_bytes()),
+                Ok((command, 2))
+            );
+        }
+        assert_eq!(
+            Command::decode_for(Controller::St7571, &[0b01000000, 0b10000000]),
+            Err(DecodeError::InvalidArgument {
+                command: 0b01000000,
+                argument: 0b10000000
+            })
+        );
+        assert_eq!(
+            Command::decode_for(Controller::St7571, &[0b01000001]),
+            Err(DecodeError::UnknownCommand { byte: 0b01000001 })
+        );
+        assert_eq!(
+            Command::decode_for(Controller::St7565, &[0b10101011]),
+            Err(DecodeError::UnknownCommand { byte: 0b10101011 })
+        );
+
+        let bytes = [0b01000000, 0b01110000, 0b01111011, 0b00010000, 0b00000000];
+        let mut decoder = Decoder::new_for(Controller::St7571, &bytes);
+        assert_eq!(
+            decoder.next(),
+            Some(Ok(Command::DisplayStartLineSet {
+                address: 0b01110000
+            }))
+        );
+        assert_eq!(
+            decoder.next(),
+            Some(Ok(Command::DisplayModeSet { grayscale: true }))
+        );
+        assert_eq!(decoder.next(), None);
     }
 
     #[test]
@@ -201,17 +354,13 @@ mod unit_tests {
 
         let empty_line = [0u8; 132];
         let expected = [
-            Command(&[0b10110000]),
-            Command(&[0b00010000, 0b00000000]),
+            Command(&[0b10110000, 0b00010000, 0b00000000]),
             Data(empty_line.as_slice()),
-            Command(&[0b10110001]),
-            Command(&[0b00010000, 0b00000000]),
+            Command(&[0b10110001, 0b00010000, 0b00000000]),
             Data(empty_line.as_slice()),
-            Command(&[0b10110010]),
-            Command(&[0b00010000, 0b00000000]),
+            Command(&[0b10110010, 0b00010000, 0b00000000]),
             Data(empty_line.as_slice()),
-            Command(&[0b10110011]),
-            Command(&[0b00010000, 0b00000000]),
+            Command(&[0b10110011, 0b00010000, 0b00000000]),
             Data(empty_line.as_slice()),
         ];
         let mut buffer = GraphicsPageBuffer::new();
@@ -230,29 +379,22 @@ mod unit_tests {
             .unwrap();
 
         let expected = [
-            Command(&[0b10110000]),
-            Command(&[0b00010110, 0b00001010]),
+            Command(&[0b10110000, 0b00010110, 0b00001010]),
             Data(&[
                 0b11000000, 0b01000000, 0b01000000, 0b01000000, 0b01000000, 0b01000000, 0b01000000,
                 0b01000000, 0b01000000, 0b01000000, 0b01000000, 0b01000000, 0b01000000, 0b01000000,
                 0b01000000, 0b01000000, 0b01000000, 0b01000000, 0b01000000, 0b11000000,
             ]),
-            Command(&[0b10110001]),
-            Command(&[0b00010110, 0b00001010]),
-            Data(&[
-                0b11111111, 0b00000000, 0b00000000, 0b00000000, 0b00000000, 0b00000000, 0b00000000,
-                0b00000000, 0b00000000, 0b00000000, 0b00000000, 0b00000000, 0b00000000, 0b00000000,
-                0b00000000, 0b00000000, 0b00000000, 0b00000000, 0b00000000, 0b11111111,
-            ]),
-            Command(&[0b10110010]),
-            Command(&[0b00010110, 0b00001010]),
-            Data(&[
-                0b11111111, 0b00000000, 0b00000000, 0b00000000, 0b00000000, 0b00000000, 0b00000000,
-                0b00000000, 0b00000000, 0b00000000, 0b00000000, 0b00000000, 0b00000000, 0b00000000,
-                0b00000000, 0b00000000, 0b00000000, 0b00000000, 0b00000000, 0b11111111,
-            ]),
-            Command(&[0b10110011]),
-            Command(&[0b00010110, 0b00001010]),
+            // Only the edges changed, which is cheaper to send separately
+            Command(&[0b10110001, 0b00010110, 0b00001010]),
+            Data(&[0b11111111]),
+            Command(&[0b00010111, 0b00001101]),
+            Data(&[0b11111111]),
+            Command(&[0b10110010, 0b00010110, 0b00001010]),
+            Data(&[0b11111111]),
+            Command(&[0b00010111, 0b00001101]),
+            Data(&[0b11111111]),
+            Command(&[0b10110011, 0b00010110, 0b00001010]),
             Data(&[
                 0b00000011, 0b00000010, 0b00000010, 0b00000010, 0b00000010, 0b00000010, 0b00000010,
                 0b00000010, 0b00000010, 0b00000010, 0b00000010, 0b00000010, 0b00000010, 0b00000010,
@@ -272,14 +414,12 @@ mod unit_tests {
             .unwrap();
 
         let expected = [
-            Command(&[0b10110001]),
-            Command(&[0b00010000, 0b00000101]),
+            Command(&[0b10110001, 0b00010000, 0b00000101]),
             Data(&[
                 0b11100000, 0b11111000, 0b00011100, 0b00001100, 0b00000110, 0b00000110, 0b00000110,
                 0b00000110, 0b00001100, 0b00011100, 0b11111000, 0b11100000,
             ]),
-            Command(&[0b10110010]),
-            Command(&[0b00010000, 0b00000101]),
+            Command(&[0b10110010, 0b00010000, 0b00000101]),
             Data(&[
                 0b00000001, 0b00000111, 0b00001110, 0b00001100, 0b00011000, 0b00011000, 0b00011000,
                 0b00011000, 0b00001100, 0b00001110, 0b00000111, 0b00000001,
@@ -290,4 +430,1937 @@ mod unit_tests {
             disp.flush().unwrap();
         });
     }
+
+    #[test]
+    fn rotation() {
+        use crate::types::Rotation;
+        use embedded_graphics::{pixelcolor::BinaryColor, prelude::*};
+
+        let empty_line = [0u8; 132];
+        let expected = [
+            Command(&[0b10110000, 0b00010000, 0b00000000]),
+            Data(empty_line.as_slice()),
+            Command(&[0b10110001, 0b00010000, 0b00000000]),
+            Data(empty_line.as_slice()),
+            Command(&[0b10110010, 0b00010000, 0b00000000]),
+            Data(empty_line.as_slice()),
+            Command(&[0b10110011, 0b00010000, 0b00000000]),
+            Data(empty_line.as_slice()),
+        ];
+        let mut buffer = GraphicsPageBuffer::new();
+        let mut disp = DisplayMock::with_expect(&expected, |disp_mock| {
+            let mut disp = ST7565::new(disp_mock, DOGM132W5).into_graphics_mode(&mut buffer);
+            disp.flush().unwrap();
+            disp.release_display_interface().0
+        });
+        assert_eq!(disp.rotation(), Rotation::Rotate0);
+
+        let cases: [(Rotation, Size, u8, [u8; 2], u8); 4] = [
+            (
+                Rotation::Rotate0,
+                Size::new(132, 32),
+                0,
+                [0x10, 0x01],
+                0b00000100,
+            ),
+            (
+                Rotation::Rotate90,
+                Size::new(32, 132),
+                0,
+                [0x18, 0x01],
+                0b00000010,
+            ),
+            (
+                Rotation::Rotate180,
+                Size::new(132, 32),
+                3,
+                [0x18, 0x02],
+                0b00100000,
+            ),
+            (
+                Rotation::Rotate270,
+                Size::new(32, 132),
+                3,
+                [0x10, 0x02],
+                0b01000000,
+            ),
+        ];
+
+        for (rotation, size, page, column, data) in cases {
+            disp.set_rotation(rotation);
+            assert_eq!(disp.size(), size);
+
+            for (color, data) in [(BinaryColor::On, data), (BinaryColor::Off, 0)] {
+                Pixel(Point::new(1, 2), color).draw(&mut disp).unwrap();
+                // Outside of the rotated display
+                Pixel(Point::new(size.width as i32, 0), BinaryColor::On)
+                    .draw(&mut disp)
+                    .unwrap();
+
+                let expected = [
+                    Command(&[0b10110000 | page, column[0], column[1]]),
+                    Data(&[data]),
+                ];
+                disp = DisplayMock::with_expect(&expected, |disp_mock| {
+                    let mut disp = disp.attach_display_interface(disp_mock);
+                    disp.flush().unwrap();
+                    disp.release_display_interface().0
+                });
+            }
+        }
+    }
+
+    #[test]
+    fn fast_fills() {
+        use crate::{mock::Recording, types::Rotation};
+        use embedded_graphics::{pixelcolor::BinaryColor, prelude::*, primitives::Rectangle};
+
+        /// Only forwards `draw_iter`, so all fills use the pixel by pixel default implementations
+        struct PixelByPixel<'d, D>(&'d mut D);
+        impl<D: DrawTarget> DrawTarget for PixelByPixel<'_, D> {
+            type Color = D::Color;
+            type Error = D::Error;
+
+            fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+            where
+                I: IntoIterator<Item = Pixel<Self::Color>>,
+            {
+                self.0.draw_iter(pixels)
+            }
+        }
+        impl<D: DrawTarget> Dimensions for PixelByPixel<'_, D> {
+            fn bounding_box(&self) -> Rectangle {
+                self.0.bounding_box()
+            }
+        }
+
+        fn scene<D>(target: &mut D)
+        where
+            D: DrawTarget<Color = BinaryColor>,
+            D::Error: core::fmt::Debug,
+        {
+            use BinaryColor::{Off, On};
+
+            target.clear(On).unwrap();
+            let area = Rectangle::new(Point::new(3, 5), Size::new(20, 13));
+            target.fill_solid(&area, Off).unwrap();
+            let area = Rectangle::new(Point::new(6, 7), Size::new(5, 1));
+            target.fill_solid(&area, On).unwrap();
+            let area = Rectangle::new(Point::new(-4, 9), Size::new(10, 30));
+            target.fill_solid(&area, Off).unwrap();
+            let area = Rectangle::new(Point::new(25, 2), Size::new(7, 9));
+            let pattern = (0..).map(|i: u32| if i % 3 == 0 { On } else { Off });
+            target.fill_contiguous(&area, pattern).unwrap();
+            let area = Rectangle::new(Point::new(-2, -2), Size::new(4, 4));
+            target
+                .fill_contiguous(&area, core::iter::repeat(Off))
+                .unwrap();
+        }
+
+        fn render(
+            buffer: &mut GraphicsPageBuffer<132, 4>,
+            rotation: Rotation,
+            pixel_by_pixel: bool,
+        ) {
+            let mut disp = ST7565::new(DisplayMock::new(&[]), DOGM132W5).into_graphics_mode(buffer);
+            disp.set_rotation(rotation);
+            if pixel_by_pixel {
+                scene(&mut PixelByPixel(&mut disp));
+            } else {
+                scene(&mut disp);
+            }
+        }
+
+        // The fills produce the same content as drawing pixel by pixel
+        for rotation in [
+            Rotation::Rotate0,
+            Rotation::Rotate90,
+            Rotation::Rotate180,
+            Rotation::Rotate270,
+        ]
+        .iter()
+        .copied()
+        {
+            let mut fast = GraphicsPageBuffer::new();
+            let mut slow = GraphicsPageBuffer::new();
+            render(&mut fast, rotation, false);
+            render(&mut slow, rotation, true);
+            for (fast, slow) in fast.pages.iter().zip(slow.pages.iter()) {
+                assert_eq!(fast.data, slow.data);
+            }
+        }
+
+        // The dirty spans only cover the bytes that changed
+        let mut recording = Recording::<2048>::new();
+        let mut buffer = GraphicsPageBuffer::new();
+        let mut disp = ST7565::new(DisplayMock::recording(&mut recording), DOGM132W5)
+            .into_graphics_mode(&mut buffer);
+        disp.flush().unwrap();
+        disp.clear(BinaryColor::Off).unwrap();
+        let area = Rectangle::new(Point::new(10, 4), Size::new(10, 8));
+        disp.fill_solid(&area, BinaryColor::On).unwrap();
+        disp.fill_solid(&area, BinaryColor::On).unwrap();
+
+        assert_eq!(buffer.pages[0].dirty.spans(), &[(10, 20)]);
+        assert_eq!(buffer.pages[0].data[10], 0b11110000);
+        assert_eq!(buffer.pages[1].dirty.spans(), &[(10, 20)]);
+        assert_eq!(buffer.pages[1].data[19], 0b00001111);
+        assert!(buffer.pages[2].dirty.is_empty());
+        assert!(buffer.pages[3].dirty.is_empty());
+    }
+
+    #[test]
+    fn page_band_mode() {
+        use crate::{displays::DOGL128_6_EXT12V, mock::Recording, PageBandBuffer};
+        use embedded_graphics::{
+            pixelcolor::BinaryColor,
+            prelude::*,
+            primitives::{Circle, PrimitiveStyle, Rectangle},
+        };
+
+        fn scene<D>(target: &mut D)
+        where
+            D: DrawTarget<Color = BinaryColor>,
+            D::Error: core::fmt::Debug,
+        {
+            Circle::new(Point::new(30, 10), 40)
+                .into_styled(PrimitiveStyle::with_stroke(BinaryColor::On, 3))
+                .draw(target)
+                .unwrap();
+            Rectangle::new(Point::new(70, 20), Size::new(50, 50))
+                .into_styled(PrimitiveStyle::with_fill(BinaryColor::On))
+                .draw(target)
+                .unwrap();
+        }
+
+        let mut expected = Recording::<4096>::new();
+        let mut buffer = GraphicsPageBuffer::new();
+        let mut disp = ST7565::new(DisplayMock::recording(&mut expected), DOGL128_6_EXT12V)
+            .into_graphics_mode(&mut buffer);
+        scene(&mut disp);
+        disp.flush().unwrap();
+
+        // Bands of three pages; the last band is only two pages tall
+        let mut recording = Recording::<4096>::new();
+        let mut buffer = PageBandBuffer::<128, 3>::new();
+        let mut disp = ST7565::new(DisplayMock::recording(&mut recording), DOGL128_6_EXT12V)
+            .into_page_band_mode(&mut buffer);
+        let mut areas = [Rectangle::zero(); 3];
+        let mut bands = 0;
+        disp.render(|band| {
+            areas[bands] = band.area();
+            bands += 1;
+            scene(band);
+        })
+        .unwrap();
+
+        assert_eq!(bands, 3);
+        assert_eq!(
+            areas,
+            [
+                Rectangle::new(Point::new(0, 0), Size::new(128, 24)),
+                Rectangle::new(Point::new(0, 24), Size::new(128, 24)),
+                Rectangle::new(Point::new(0, 48), Size::new(128, 16)),
+            ]
+        );
+        assert!(recording.actions().eq(expected.actions()));
+    }
+
+    #[test]
+    fn direct_graphics_mode() {
+        #[cfg(feature = "emulator")]
+        {
+            use crate::{displays::DOGL128_6_EXT12V, emulator::Emulator};
+            use embedded_graphics::{
+                pixelcolor::BinaryColor,
+                prelude::*,
+                primitives::{Circle, PrimitiveStyle, Rectangle},
+            };
+
+            fn scene<D>(target: &mut D)
+            where
+                D: DrawTarget<Color = BinaryColor>,
+                D::Error: core::fmt::Debug,
+            {
+                target.clear(BinaryColor::On).unwrap();
+                Rectangle::new(Point::new(5, 3), Size::new(60, 30))
+                    .into_styled(PrimitiveStyle::with_fill(BinaryColor::Off))
+                    .draw(target)
+                    .unwrap();
+                Circle::new(Point::new(30, 10), 40)
+                    .into_styled(PrimitiveStyle::with_stroke(BinaryColor::On, 3))
+                    .draw(target)
+                    .unwrap();
+                Pixel(Point::new(127, 63), BinaryColor::Off)
+                    .draw(target)
+                    .unwrap();
+                Pixel(Point::new(128, 0), BinaryColor::Off)
+                    .draw(target)
+                    .unwrap();
+            }
+
+            let mut expected = Emulator::new(DOGL128_6_EXT12V);
+            let mut buffer = GraphicsPageBuffer::new();
+            let mut disp =
+                ST7565::new(&mut expected, DOGL128_6_EXT12V).into_graphics_mode(&mut buffer);
+            scene(&mut disp);
+            disp.flush().unwrap();
+
+            let mut emulator = Emulator::new(DOGL128_6_EXT12V);
+            let mut disp = ST7565::new(&mut emulator, DOGL128_6_EXT12V).into_direct_graphics_mode();
+            scene(&mut disp);
+
+            assert_eq!(emulator.ddram(), expected.ddram());
+            // The column address is restored after every read-modify-write sequence
+            assert_eq!(emulator.column_address(), 4 + 127);
+        }
+    }
+
+    #[test]
+    fn status_read() {
+        #[cfg(feature = "emulator")]
+        {
+            use super::dummy_hal::{DummyDelay, DummyPin};
+            use crate::{
+                displays::DOGL128_6_EXT12V, emulator::Emulator, types::Status, HealthCheckError,
+            };
+            use embedded_graphics::{pixelcolor::BinaryColor, prelude::*};
+
+            let mut emulator = Emulator::new(DOGL128_6_EXT12V);
+            let mut buffer = GraphicsPageBuffer::new();
+            let mut disp =
+                ST7565::new(&mut emulator, DOGL128_6_EXT12V).into_graphics_mode(&mut buffer);
+            disp.reset(&mut DummyPin, &mut DummyDelay).unwrap();
+            assert_eq!(
+                disp.check_health().unwrap(),
+                Status {
+                    busy: false,
+                    adc_reverse: false,
+                    display_on: false,
+                    resetting: false,
+                }
+            );
+            disp.set_display_on(true).unwrap();
+            assert!(disp.read_status().unwrap().display_on);
+            assert!(disp.check_health().is_ok());
+
+            for y in 8..16 {
+                Pixel(Point::new(12, y), BinaryColor::On)
+                    .draw(&mut disp)
+                    .unwrap();
+            }
+            Pixel(Point::new(13, 9), BinaryColor::On)
+                .draw(&mut disp)
+                .unwrap();
+            disp.flush().unwrap();
+
+            let mut data = [0xAA; 128];
+            disp.read_page(1, 11..14, &mut data).unwrap();
+            assert_eq!(data[10], 0xAA);
+            assert_eq!(&data[11..14], &[0b00000000, 0b11111111, 0b00000010]);
+            assert_eq!(data[14], 0xAA);
+
+            // A display that lost its configuration is detected
+            let mut disp = ST7565::new(&mut emulator, DOGL128_6_EXT12V).into_raw_mode();
+            disp.adc_select(true).unwrap();
+            assert!(matches!(
+                disp.check_health(),
+                Err(HealthCheckError::UnexpectedStatus(Status {
+                    adc_reverse: true,
+                    ..
+                }))
+            ));
+        }
+    }
+
+    #[test]
+    fn parallel_interface() {
+        #[cfg(feature = "emulator")]
+        {
+            use super::{
+                dummy_hal::{DummyDelay, DummyPin},
+                parallel_bus::{WirePin, Wires, A0, CS, RD, WR},
+            };
+            use crate::{
+                emulator::Emulator,
+                interface::parallel::{DataBus, ParallelInterface, Protocol, STROBE_PHASE_NS},
+            };
+            use core::cell::{Cell, RefCell};
+            use embedded_graphics::{pixelcolor::BinaryColor, prelude::*};
+
+            /// Sums up the delays of the strobes
+            struct StrobeDelay<'a>(&'a Cell<u32>);
+            impl embedded_hal::delay::DelayNs for StrobeDelay<'_> {
+                fn delay_ns(&mut self, ns: u32) {
+                    self.0.set(self.0.get() + ns);
+                }
+            }
+
+            for protocol in [Protocol::Intel8080, Protocol::Motorola6800]
+                .iter()
+                .copied()
+            {
+                let mut emulator = Emulator::new(DOGM132W5);
+                let delays = Cell::new(0);
+                {
+                    let wires = RefCell::new(Wires::new(protocol, &mut emulator));
+                    let pin = |index| WirePin {
+                        wires: &wires,
+                        index,
+                    };
+                    let bus = DataBus::new(
+                        pin(0),
+                        pin(1),
+                        pin(2),
+                        pin(3),
+                        pin(4),
+                        pin(5),
+                        pin(6),
+                        pin(7),
+                    );
+                    let interface = match protocol {
+                        Protocol::Intel8080 => {
+                            ParallelInterface::new_8080(bus, pin(A0), pin(WR), pin(RD), pin(CS))
+                        }
+                        Protocol::Motorola6800 => {
+                            ParallelInterface::new_6800(bus, pin(A0), pin(WR), pin(RD), pin(CS))
+                        }
+                    };
+                    assert_eq!(interface.protocol(), protocol);
+                    let interface = interface.with_delay(StrobeDelay(&delays));
+
+                    let mut buffer = GraphicsPageBuffer::new();
+                    let mut disp =
+                        ST7565::new(interface, DOGM132W5).into_graphics_mode(&mut buffer);
+                    disp.reset(&mut DummyPin, &mut DummyDelay).unwrap();
+                    disp.set_display_on(true).unwrap();
+                    Pixel(Point::new(3, 13), BinaryColor::On)
+                        .draw(&mut disp)
+                        .unwrap();
+                    disp.flush().unwrap();
+
+                    assert!(disp.check_health().unwrap().display_on);
+                    let mut data = [0; 132];
+                    disp.read_page(1, 2..5, &mut data).unwrap();
+                    assert_eq!(&data[2..5], &[0, 0b00100000, 0]);
+                }
+                // Every byte holds both phases of its strobe
+                assert!(delays.get() > 2 * 132 * STROBE_PHASE_NS);
+
+                assert!(emulator.display_on());
+                assert!(emulator.pixel(3, 13));
+                assert!(!emulator.pixel(4, 13));
+            }
+        }
+    }
+
+    #[test]
+    fn spi_interface() {
+        use crate::interface::spi::SpiInterface;
+        use core::{cell::Cell, convert::Infallible};
+        use embedded_hal::spi::{ErrorType, Operation, SpiDevice};
+
+        struct A0Pin<'a>(&'a Cell<bool>);
+        impl embedded_hal::digital::ErrorType for A0Pin<'_> {
+            type Error = Infallible;
+        }
+        impl embedded_hal::digital::OutputPin for A0Pin<'_> {
+            fn set_low(&mut self) -> Result<(), Self::Error> {
+                self.0.set(false);
+                Ok(())
+            }
+            fn set_high(&mut self) -> Result<(), Self::Error> {
+                self.0.set(true);
+                Ok(())
+            }
+        }
+
+        /// Records the A0 level, length and first byte of every transaction
+        struct Spi<'a> {
+            a0: &'a Cell<bool>,
+            transactions: [(bool, usize, u8); 16],
+            count: usize,
+        }
+        impl ErrorType for Spi<'_> {
+            type Error = Infallible;
+        }
+        impl SpiDevice for Spi<'_> {
+            fn transaction(
+                &mut self,
+                operations: &mut [Operation<'_, u8>],
+            ) -> Result<(), Infallible> {
+                for operation in operations.iter() {
+                    if let Operation::Write(bytes) = operation {
+                        self.transactions[self.count] = (self.a0.get(), bytes.len(), bytes[0]);
+                        self.count += 1;
+                    }
+                }
+                Ok(())
+            }
+        }
+
+        let a0 = Cell::new(false);
+        let spi = Spi {
+            a0: &a0,
+            transactions: [(false, 0, 0); 16],
+            count: 0,
+        };
+
+        let mut buffer = GraphicsPageBuffer::new();
+        let mut disp = ST7565::new(SpiInterface::new(spi, A0Pin(&a0)), DOGM132W5)
+            .into_graphics_mode(&mut buffer);
+        disp.flush().unwrap();
+        let (spi, _) = disp.release_display_interface().1.release();
+
+        // One transaction for the page and column address, one for the data
+        assert_eq!(spi.count, 8);
+        for (page, transactions) in spi.transactions[..8].chunks(2).enumerate() {
+            assert_eq!(transactions[0], (false, 3, 0b10110000 | page as u8));
+            assert_eq!(transactions[1], (true, 132, 0));
+        }
+    }
+
+    #[test]
+    fn i2c_interface() {
+        use crate::{displays::ST7567A_12864, interface::i2c::I2cInterface};
+        use core::convert::Infallible;
+        use embedded_hal::i2c::{ErrorType, I2c, Operation};
+
+        /// Passes the address, the control byte and the payload of every transaction to a callback
+        struct Bus<F>(F);
+        impl<F> ErrorType for Bus<F> {
+            type Error = Infallible;
+        }
+        impl<F: FnMut(u8, u8, &[u8])> I2c for Bus<F> {
+            fn transaction(
+                &mut self,
+                address: u8,
+                operations: &mut [Operation<'_>],
+            ) -> Result<(), Infallible> {
+                let mut bytes = [0; 256];
+                let mut len = 0;
+                for operation in operations.iter() {
+                    if let Operation::Write(data) = operation {
+                        bytes[len..len + data.len()].copy_from_slice(data);
+                        len += data.len();
+                    }
+                }
+                (self.0)(address, bytes[0], &bytes[1..len]);
+                Ok(())
+            }
+        }
+
+        let mut transactions = 0;
+        {
+            let bus = Bus(|address: u8, control: u8, payload: &[u8]| {
+                assert_eq!(address, 0x3C);
+                match transactions % 2 {
+                    0 => {
+                        assert_eq!(control, 0b0000_0000);
+                        assert_eq!(
+                            payload,
+                            &[0b10110000 | (transactions / 2) as u8, 0b00010000, 0]
+                        );
+                    }
+                    _ => {
+                        assert_eq!(control, 0b0100_0000);
+                        assert_eq!(payload, &[0; 128][..]);
+                    }
+                }
+                transactions += 1;
+            });
+            let interface = I2cInterface::new(bus).with_address(0x3C);
+            let mut buffer = GraphicsPageBuffer::new();
+            ST7565::new(interface, ST7567A_12864)
+                .into_graphics_mode(&mut buffer)
+                .flush()
+                .unwrap();
+        }
+        assert_eq!(transactions, 16);
+
+        #[cfg(feature = "emulator")]
+        {
+            use super::dummy_hal::{DummyDelay, DummyPin};
+            use crate::emulator::Emulator;
+            use display_interface::{DataFormat::U8, WriteOnlyDataCommand};
+            use embedded_graphics::{pixelcolor::BinaryColor, prelude::*};
+
+            let mut emulator = Emulator::new(ST7567A_12864);
+            {
+                let bus = Bus(|address: u8, control: u8, payload: &[u8]| {
+                    assert_eq!(address, I2cInterface::<()>::DEFAULT_ADDRESS);
+                    match control {
+                        0b0000_0000 => emulator.send_commands(U8(payload)).unwrap(),
+                        0b0100_0000 => emulator.send_data(U8(payload)).unwrap(),
+                        _ => panic!("Unexpected control byte"),
+                    }
+                });
+                let mut buffer = GraphicsPageBuffer::new();
+                let mut disp = ST7565::new(I2cInterface::new(bus), ST7567A_12864)
+                    .into_graphics_mode(&mut buffer);
+                disp.reset(&mut DummyPin, &mut DummyDelay).unwrap();
+                disp.set_display_on(true).unwrap();
+                Pixel(Point::new(100, 50), BinaryColor::On)
+                    .draw(&mut disp)
+                    .unwrap();
+                disp.flush().unwrap();
+            }
+            assert!(emulator.display_on());
+            assert!(emulator.pixel(100, 50));
+            assert!(!emulator.pixel(101, 50));
+        }
+    }
+
+    #[test]
+    fn spi_9bit_interface() {
+        use super::dummy_hal::{DummyDelay, DummyPin};
+        use crate::{displays::DOGS102_6, interface::spi_9bit::Spi9BitInterface};
+        use core::convert::Infallible;
+        use embedded_hal::spi::{ErrorType, Operation, SpiDevice};
+
+        /// Unpacks the 9-bit frames of every transaction and passes them to a callback
+        struct Spi<F>(F);
+        impl<F> ErrorType for Spi<F> {
+            type Error = Infallible;
+        }
+        impl<F: FnMut(bool, u8)> SpiDevice for Spi<F> {
+            fn transaction(
+                &mut self,
+                operations: &mut [Operation<'_, u8>],
+            ) -> Result<(), Infallible> {
+                for operation in operations.iter() {
+                    if let Operation::Write(bytes) = operation {
+                        // Every transaction consists of whole frames
+                        assert_eq!(bytes.len() % 9, 0);
+                        for frame in 0..bytes.len() * 8 / 9 {
+                            let value = (0..9).fold(0u16, |value, index| {
+                                let bit = frame * 9 + index;
+                                (value << 1) | u16::from((bytes[bit / 8] >> (7 - bit % 8)) & 1)
+                            });
+                            (self.0)(value & 0x100 != 0, value as u8);
+                        }
+                    }
+                }
+                Ok(())
+            }
+        }
+
+        let mut commands = [0u8; 16];
+        let mut len = 0;
+        {
+            let spi = Spi(|a0: bool, byte: u8| {
+                assert!(!a0);
+                // Skip the padding
+                if byte != 0b11100011 {
+                    commands[len] = byte;
+                    len += 1;
+                }
+            });
+            let mut disp = ST7565::new(Spi9BitInterface::new(spi), DOGS102_6).into_raw_mode();
+            disp.reset(&mut DummyPin, &mut DummyDelay).unwrap();
+        }
+        assert_eq!(
+            &commands[..len],
+            &[
+                0b10100010, 0b10100001, 0b11000000, 0b10100110, 0b00100111, 0b10000001, 0b00010000,
+                0b11111010, 0b10010000, 0b00101111, 0b01000000,
+            ]
+        );
+
+        #[cfg(feature = "emulator")]
+        {
+            use crate::emulator::Emulator;
+            use display_interface::{DataFormat::U8, WriteOnlyDataCommand};
+            use embedded_graphics::{pixelcolor::BinaryColor, prelude::*};
+
+            let mut emulator = Emulator::new(DOGS102_6);
+            {
+                let spi = Spi(|a0: bool, byte: u8| match a0 {
+                    false => emulator.send_commands(U8(&[byte])).unwrap(),
+                    true => emulator.send_data(U8(&[byte])).unwrap(),
+                });
+                let mut buffer = GraphicsPageBuffer::new();
+                let mut disp = ST7565::new(Spi9BitInterface::new(spi), DOGS102_6)
+                    .into_graphics_mode(&mut buffer);
+                disp.reset(&mut DummyPin, &mut DummyDelay).unwrap();
+                disp.set_display_on(true).unwrap();
+                Pixel(Point::new(101, 63), BinaryColor::On)
+                    .draw(&mut disp)
+                    .unwrap();
+                disp.flush().unwrap();
+            }
+            assert!(emulator.display_on());
+            assert_eq!(emulator.advanced_program_control(), Some(0b10010000));
+            assert!(emulator.pixel(101, 63));
+            assert!(!emulator.pixel(100, 63));
+        }
+    }
+
+    #[test]
+    fn terminal_mode() {
+        use crate::mock::Recording;
+        use core::fmt::Write;
+
+        let mut recording = Recording::<2048>::new();
+        let mut disp_mock = DisplayMock::recording(&mut recording);
+        let mut disp = ST7565::new(&mut disp_mock, DOGM132W5).into_terminal_mode();
+        disp.clear().unwrap();
+        write!(disp, "ab").unwrap();
+        // Row 3 is the last one; the fourth newline scrolls
+        write!(disp, "\n\n\n").unwrap();
+        assert_eq!(disp.cursor(), (0, 3));
+        writeln!(disp).unwrap();
+        write!(disp, "c").unwrap();
+        assert_eq!(disp.cursor(), (1, 3));
+
+        let empty_line = [0u8; 132];
+        let expected = [
+            Command(&[0b10110000, 0b00010000, 0b00000000]),
+            Data(&[0x30, 0x48, 0x48, 0x78, 0x00, 0x00]),
+            Data(&[0x7E, 0x48, 0x48, 0x30, 0x00, 0x00]),
+            Command(&[0b10110100, 0b00010000, 0b00000000]),
+            Data(empty_line.as_slice()),
+            Command(&[0b01001000]),
+            Command(&[0b10110100, 0b00010000, 0b00000000]),
+            Data(&[0x00, 0x30, 0x48, 0x48, 0x00, 0x00]),
+        ];
+        assert!(recording.actions().skip(17).eq(expected));
+
+        #[cfg(feature = "emulator")]
+        {
+            use super::dummy_hal::{DummyDelay, DummyPin};
+            use crate::{displays::DOGL128_6_EXT12V, emulator::Emulator};
+
+            fn print(text: &str) -> [[bool; 128]; 64] {
+                let mut emulator = Emulator::new(DOGL128_6_EXT12V);
+                let mut disp = ST7565::new(&mut emulator, DOGL128_6_EXT12V).into_terminal_mode();
+                disp.reset(&mut DummyPin, &mut DummyDelay).unwrap();
+                disp.set_display_on(true).unwrap();
+                disp.clear().unwrap();
+                write!(disp, "{}", text).unwrap();
+                emulator.pixels()
+            }
+
+            let before = print("a\nb\nc\nd\ne\nf\ng\nh");
+            let after = print("a\nb\nc\nd\ne\nf\ng\nh\ni");
+
+            assert_eq!(after[..56], before[8..]);
+            assert!(after[56..].iter().flatten().any(|&pixel| pixel));
+        }
+
+        // The ST7571 scrolls through all 16 pages of its display RAM
+        {
+            use crate::displays::ST7571_128128;
+
+            let mut recording = Recording::<4096>::new();
+            let mut disp_mock = DisplayMock::recording(&mut recording);
+            let mut disp = ST7565::new(&mut disp_mock, ST7571_128128).into_terminal_mode();
+            disp.clear().unwrap();
+            // The 16th newline scrolls for the first time
+            for _ in 0..24 {
+                writeln!(disp).unwrap();
+            }
+            assert_eq!(disp.cursor(), (0, 15));
+
+            let empty_line = [0u8; 128];
+            let mut actions = recording.actions();
+            for page in 0..16 {
+                assert_eq!(
+                    actions.next(),
+                    Some(Command(&[0b10110000 | page, 0b00010000, 0b00000000]))
+                );
+                assert_eq!(actions.next(), Some(Data(empty_line.as_slice())));
+            }
+            assert_eq!(actions.next(), Some(Command(&[0b01000000, 0b00000000])));
+            let expected = [
+                Command(&[0b10111000, 0b00010000, 0b00000000]),
+                Data(empty_line.as_slice()),
+                Command(&[0b01000000, 0b01001000]),
+            ];
+            assert!(actions.skip(8 * 3).eq(expected));
+        }
+    }
+
+    #[test]
+    fn double_buffering() {
+        use embedded_graphics::{pixelcolor::BinaryColor, prelude::*};
+
+        let empty_line = [0u8; 132];
+        let expected = [
+            Command(&[0b10110100, 0b00010000, 0b00000000]),
+            Data(empty_line.as_slice()),
+            Command(&[0b10110101, 0b00010000, 0b00000000]),
+            Data(empty_line.as_slice()),
+            Command(&[0b10110110, 0b00010000, 0b00000000]),
+            Data(empty_line.as_slice()),
+            Command(&[0b10110111, 0b00010000, 0b00000000]),
+            Data(empty_line.as_slice()),
+            Command(&[0b01100000]),
+        ];
+
+        // The first frame goes to the second half of the RAM
+        let mut buffer = GraphicsPageBuffer::new();
+        let mut disp = DisplayMock::with_expect(&expected, |disp_mock| {
+            let mut disp =
+                ST7565::new(disp_mock, DOGM132W5).into_double_buffered_graphics_mode(&mut buffer);
+            disp.flush().unwrap();
+            disp.release_display_interface().0
+        });
+
+        // The first half still needs the full first frame
+        Pixel(Point::new(3, 1), BinaryColor::On)
+            .draw(&mut disp)
+            .unwrap();
+        let mut first_line = [0u8; 132];
+        first_line[3] = 0b00000010;
+        let expected = [
+            Command(&[0b10110000, 0b00010000, 0b00000000]),
+            Data(first_line.as_slice()),
+            Command(&[0b10110001, 0b00010000, 0b00000000]),
+            Data(empty_line.as_slice()),
+            Command(&[0b10110010, 0b00010000, 0b00000000]),
+            Data(empty_line.as_slice()),
+            Command(&[0b10110011, 0b00010000, 0b00000000]),
+            Data(empty_line.as_slice()),
+            Command(&[0b01000000]),
+        ];
+        let mut disp = DisplayMock::with_expect(&expected, |disp_mock| {
+            let mut disp = disp.attach_display_interface(disp_mock);
+            disp.flush().unwrap();
+            disp.release_display_interface().0
+        });
+
+        // The second half is missing the previous and the current change
+        Pixel(Point::new(5, 1), BinaryColor::On)
+            .draw(&mut disp)
+            .unwrap();
+        let expected = [
+            Command(&[0b10110100, 0b00010000, 0b00000011]),
+            Data(&[0b00000010, 0b00000000, 0b00000010]),
+            Command(&[0b01100000]),
+        ];
+        let disp = DisplayMock::with_expect(&expected, |disp_mock| {
+            let mut disp = disp.attach_display_interface(disp_mock);
+            disp.flush().unwrap();
+            disp.release_display_interface().0
+        });
+
+        // A failed frame does not get displayed and is retried completely
+        let expected = [
+            Command(&[0b10110000, 0b00010000, 0b00000101]),
+            Data(&[0b00000010]),
+        ];
+        let mut disp_mock =
+            DisplayMock::new(&expected).fail_at(1, display_interface::DisplayError::BusWriteError);
+        let mut disp = disp.attach_display_interface(&mut disp_mock);
+        assert_eq!(disp.flush().unwrap_err().page, 0);
+        let disp = disp.release_display_interface().0;
+        disp_mock.verify();
+
+        let expected = [
+            Command(&[0b10110000, 0b00010000, 0b00000101]),
+            Data(&[0b00000010]),
+            Command(&[0b01000000]),
+        ];
+        DisplayMock::with_expect(&expected, |disp_mock| {
+            let mut disp = disp.attach_display_interface(disp_mock);
+            disp.flush().unwrap();
+        });
+
+        // The display RAM of the ST7571 has room for two frames of 64 lines
+        {
+            use crate::{
+                mock::Recording,
+                types::{BoosterRatio, Controller, PowerControlMode},
+                DisplaySpecs,
+            };
+
+            struct St7571Display;
+            impl DisplaySpecs<128, 64, 8> for St7571Display {
+                const FLIP_ROWS: bool = true;
+                const FLIP_COLUMNS: bool = false;
+                const INVERTED: bool = false;
+                const BIAS_MODE_1: bool = false;
+                const POWER_CONTROL: PowerControlMode = PowerControlMode {
+                    booster_circuit: true,
+                    voltage_regulator_circuit: true,
+                    voltage_follower_circuit: true,
+                };
+                const VOLTAGE_REGULATOR_RESISTOR_RATIO: u8 = 0b111;
+                const ELECTRONIC_VOLUME: u8 = 0b110100;
+                const BOOSTER_RATIO: BoosterRatio = BoosterRatio::StepUp2x3x4x;
+                const COLUMN_OFFSET: u8 = 0;
+                const CONTROLLER: Controller = Controller::St7571;
+            }
+
+            let mut recording = Recording::<2048>::new();
+            let mut disp_mock = DisplayMock::recording(&mut recording);
+            let mut buffer = GraphicsPageBuffer::new();
+            let mut disp = ST7565::new(&mut disp_mock, St7571Display)
+                .into_double_buffered_graphics_mode(&mut buffer);
+            disp.flush().unwrap();
+
+            let empty_line = [0u8; 128];
+            let mut actions = recording.actions();
+            for page in 8..16 {
+                assert_eq!(
+                    actions.next(),
+                    Some(Command(&[0b10110000 | page, 0b00010000, 0b00000000]))
+                );
+                assert_eq!(actions.next(), Some(Data(empty_line.as_slice())));
+            }
+            assert_eq!(actions.next(), Some(Command(&[0b01000000, 0b01000000])));
+            assert_eq!(actions.next(), None);
+        }
+    }
+
+    #[test]
+    fn dirty_spans() {
+        use embedded_graphics::{pixelcolor::BinaryColor, prelude::*};
+
+        let empty_line = [0u8; 132];
+        let expected = [
+            Command(&[0b10110000, 0b00010000, 0b00000000]),
+            Data(empty_line.as_slice()),
+            Command(&[0b10110001, 0b00010000, 0b00000000]),
+            Data(empty_line.as_slice()),
+            Command(&[0b10110010, 0b00010000, 0b00000000]),
+            Data(empty_line.as_slice()),
+            Command(&[0b10110011, 0b00010000, 0b00000000]),
+            Data(empty_line.as_slice()),
+        ];
+        let mut buffer = GraphicsPageBuffer::new();
+        let mut disp = DisplayMock::with_expect(&expected, |disp_mock| {
+            let mut disp = ST7565::new(disp_mock, DOGM132W5).into_graphics_mode(&mut buffer);
+            disp.flush().unwrap();
+            disp.release_display_interface().0
+        });
+
+        // Far apart columns are sent separately, close ones together
+        for x in [131, 0, 10, 14] {
+            Pixel(Point::new(x, 0), BinaryColor::On)
+                .draw(&mut disp)
+                .unwrap();
+        }
+        // More than four spans; the two closest ones get merged
+        for x in [0, 30, 60, 90, 121] {
+            Pixel(Point::new(x, 8), BinaryColor::On)
+                .draw(&mut disp)
+                .unwrap();
+        }
+
+        let mut merged = [0u8; 31];
+        merged[0] = 0b00000001;
+        merged[30] = 0b00000001;
+        let expected = [
+            Command(&[0b10110000, 0b00010000, 0b00000000]),
+            Data(&[0b00000001]),
+            Command(&[0b00010000, 0b00001010]),
+            Data(&[0b00000001, 0, 0, 0, 0b00000001]),
+            Command(&[0b00011000, 0b00000011]),
+            Data(&[0b00000001]),
+            Command(&[0b10110001, 0b00010000, 0b00000000]),
+            Data(merged.as_slice()),
+            Command(&[0b00010011, 0b00001100]),
+            Data(&[0b00000001]),
+            Command(&[0b00010101, 0b00001010]),
+            Data(&[0b00000001]),
+            Command(&[0b00010111, 0b00001001]),
+            Data(&[0b00000001]),
+        ];
+        DisplayMock::with_expect(&expected, |disp_mock| {
+            let mut disp = disp.attach_display_interface(disp_mock);
+            disp.flush().unwrap();
+        });
+    }
+
+    #[test]
+    fn shadow_buffer() {
+        use embedded_graphics::{pixelcolor::BinaryColor, prelude::*};
+
+        let empty_line = [0u8; 132];
+        let full_flush = [
+            Command(&[0b10110000, 0b00010000, 0b00000000]),
+            Data(empty_line.as_slice()),
+            Command(&[0b10110001, 0b00010000, 0b00000000]),
+            Data(empty_line.as_slice()),
+            Command(&[0b10110010, 0b00010000, 0b00000000]),
+            Data(empty_line.as_slice()),
+            Command(&[0b10110011, 0b00010000, 0b00000000]),
+            Data(empty_line.as_slice()),
+        ];
+        let mut buffer = GraphicsPageBuffer::new();
+        let mut shadow = ShadowBuffer::new();
+        let mut disp = DisplayMock::with_expect(&full_flush, |disp_mock| {
+            let mut disp = ST7565::new(disp_mock, DOGM132W5)
+                .into_graphics_mode(&mut buffer)
+                .with_shadow_buffer(&mut shadow);
+            disp.flush().unwrap();
+            disp.release_display_interface().0
+        });
+
+        // A pixel toggled back to its old value causes no traffic
+        Pixel(Point::new(5, 0), BinaryColor::On)
+            .draw(&mut disp)
+            .unwrap();
+        Pixel(Point::new(5, 0), BinaryColor::Off)
+            .draw(&mut disp)
+            .unwrap();
+        let mut disp = DisplayMock::with_expect(&[], |disp_mock| {
+            let mut disp = disp.attach_display_interface(disp_mock);
+            disp.flush().unwrap();
+            disp.release_display_interface().0
+        });
+
+        // Only the bytes that differ from the display get sent
+        for x in [5, 6, 7] {
+            Pixel(Point::new(x, 8), BinaryColor::On)
+                .draw(&mut disp)
+                .unwrap();
+        }
+        Pixel(Point::new(6, 8), BinaryColor::Off)
+            .draw(&mut disp)
+            .unwrap();
+        let expected = [
+            Command(&[0b10110001, 0b00010000, 0b00000101]),
+            Data(&[0b00000001, 0, 0b00000001]),
+        ];
+        let mut disp = DisplayMock::with_expect(&expected, |disp_mock| {
+            let mut disp = disp.attach_display_interface(disp_mock);
+            disp.flush().unwrap();
+            disp.release_display_interface().0
+        });
+
+        // Changing the orientation forces a full retransmission
+        for x in [5, 7] {
+            Pixel(Point::new(x, 8), BinaryColor::Off)
+                .draw(&mut disp)
+                .unwrap();
+        }
+        let mut expected = [Command(&[]); 11];
+        expected[0] = Command(&[0b10100000]);
+        expected[1] = Command(&[0b11001000]);
+        expected[2] = Command(&[0b01100000]);
+        expected[3..].copy_from_slice(&full_flush);
+        DisplayMock::with_expect(&expected, |disp_mock| {
+            let mut disp = disp.attach_display_interface(disp_mock);
+            disp.set_orientation(crate::types::Orientation::UpsideDown)
+                .unwrap();
+            disp.flush().unwrap();
+        });
+    }
+
+    #[test]
+    fn flush_failure() {
+        use display_interface::DisplayError;
+        use embedded_graphics::{pixelcolor::BinaryColor, prelude::*};
+
+        let mut buffer = GraphicsPageBuffer::new();
+        let mut disp = DisplayMock::with_expect(&[], |disp_mock| {
+            ST7565::new(disp_mock, DOGM132W5)
+                .into_graphics_mode(&mut buffer)
+                .release_display_interface()
+                .0
+        });
+        Pixel(Point::new(3, 1), BinaryColor::On)
+            .draw(&mut disp)
+            .unwrap();
+
+        // Page 0 succeeds, page 1 fails
+        let empty_line = [0u8; 132];
+        let mut first_line = [0u8; 132];
+        first_line[3] = 0b00000010;
+        let expected = [
+            Command(&[0b10110000, 0b00010000, 0b00000000]),
+            Data(first_line.as_slice()),
+            Command(&[0b10110001, 0b00010000, 0b00000000]),
+            Data(empty_line.as_slice()),
+        ];
+        let mut disp_mock = DisplayMock::new(&expected).fail_at(3, DisplayError::BusWriteError);
+        let mut disp = disp.attach_display_interface(&mut disp_mock);
+        let err = disp.flush().unwrap_err();
+        assert_eq!(err.page, 1);
+        assert!(matches!(err.error, DisplayError::BusWriteError));
+        let disp = disp.release_display_interface().0;
+        disp_mock.verify();
+
+        // Retry continues with the failed page
+        let expected = [
+            Command(&[0b10110001, 0b00010000, 0b00000000]),
+            Data(empty_line.as_slice()),
+        ];
+        let mut disp_mock = DisplayMock::new(&expected).fail_at(1, DisplayError::BusWriteError);
+        let mut disp = disp.attach_display_interface(&mut disp_mock);
+        assert_eq!(disp.flush().unwrap_err().page, 1);
+        let disp = disp.release_display_interface().0;
+        disp_mock.verify();
+
+        let expected = [
+            Command(&[0b10110001, 0b00010000, 0b00000000]),
+            Data(empty_line.as_slice()),
+            Command(&[0b10110010, 0b00010000, 0b00000000]),
+            Data(empty_line.as_slice()),
+            Command(&[0b10110011, 0b00010000, 0b00000000]),
+            Data(empty_line.as_slice()),
+        ];
+        let disp = DisplayMock::with_expect(&expected, |disp_mock| {
+            let mut disp = disp.attach_display_interface(disp_mock);
+            disp.flush().unwrap();
+            disp.release_display_interface().0
+        });
+
+        // Everything is synchronized now
+        DisplayMock::with_expect(&[], |disp_mock| {
+            disp.attach_display_interface(disp_mock).flush().unwrap();
+        });
+
+        // With a shadow buffer, the failed page gets resent completely,
+        // even if a pixel that already reached the display was changed back
+        let full_flush = [
+            Command(&[0b10110000, 0b00010000, 0b00000000]),
+            Data(empty_line.as_slice()),
+            Command(&[0b10110001, 0b00010000, 0b00000000]),
+            Data(empty_line.as_slice()),
+            Command(&[0b10110010, 0b00010000, 0b00000000]),
+            Data(empty_line.as_slice()),
+            Command(&[0b10110011, 0b00010000, 0b00000000]),
+            Data(empty_line.as_slice()),
+        ];
+        let mut buffer = GraphicsPageBuffer::new();
+        let mut shadow = ShadowBuffer::new();
+        let mut disp = DisplayMock::with_expect(&full_flush, |disp_mock| {
+            let mut disp = ST7565::new(disp_mock, DOGM132W5)
+                .into_graphics_mode(&mut buffer)
+                .with_shadow_buffer(&mut shadow);
+            disp.flush().unwrap();
+            disp.release_display_interface().0
+        });
+        for x in [5, 40] {
+            Pixel(Point::new(x, 8), BinaryColor::On)
+                .draw(&mut disp)
+                .unwrap();
+        }
+
+        let expected = [
+            Command(&[0b10110001, 0b00010000, 0b00000101]),
+            Data(&[0b00000001]),
+            Command(&[0b00010010, 0b00001000]),
+            Data(&[0b00000001]),
+        ];
+        let mut disp_mock = DisplayMock::new(&expected).fail_at(3, DisplayError::BusWriteError);
+        let mut disp = disp.attach_display_interface(&mut disp_mock);
+        assert_eq!(disp.flush().unwrap_err().page, 1);
+        let mut disp = disp.release_display_interface().0;
+        disp_mock.verify();
+
+        Pixel(Point::new(5, 8), BinaryColor::Off)
+            .draw(&mut disp)
+            .unwrap();
+        let expected = [
+            Command(&[0b10110001, 0b00010000, 0b00000101]),
+            Data(&[0b00000000]),
+            Command(&[0b00010010, 0b00001000]),
+            Data(&[0b00000001]),
+        ];
+        let mut disp = DisplayMock::with_expect(&expected, |disp_mock| {
+            let mut disp = disp.attach_display_interface(disp_mock);
+            disp.flush().unwrap();
+            disp.release_display_interface().0
+        });
+
+        // The shadow buffer is in sync again
+        Pixel(Point::new(40, 8), BinaryColor::Off)
+            .draw(&mut disp)
+            .unwrap();
+        let expected = [
+            Command(&[0b10110001, 0b00010010, 0b00001000]),
+            Data(&[0b00000000]),
+        ];
+        DisplayMock::with_expect(&expected, |disp_mock| {
+            disp.attach_display_interface(disp_mock).flush().unwrap();
+        });
+    }
+
+    #[test]
+    fn reset() {
+        use super::dummy_hal::{DummyDelay, DummyPin};
+
+        let expected = [
+            Command(&[0b10100010]),
+            Command(&[0b10100001]),
+            Command(&[0b11000000]),
+            Command(&[0b10100110]),
+            Command(&[0b11111000, 0b00000000]),
+            Command(&[0b00100011]),
+            Command(&[0b10000001, 0b00011111]),
+            Command(&[0b00101111]),
+            Command(&[0b01000000]),
+        ];
+        DisplayMock::with_expect(&expected, |disp_mock| {
+            let mut disp = ST7565::new(disp_mock, DOGM132W5).into_raw_mode();
+            disp.reset(&mut DummyPin, &mut DummyDelay).unwrap();
+        });
+    }
+
+    #[test]
+    fn display_presets() {
+        use super::dummy_hal::{DummyDelay, DummyPin};
+        use crate::{
+            displays::{
+                DOGL128_6, DOGM128_6, ERC12864, JLX12864, LM6059, NHD_C12832A1Z, NHD_C12864,
+            },
+            mock::ExpectedAction,
+            DisplaySpecs,
+        };
+
+        fn check_reset<SPECS, const WIDTH: usize, const HEIGHT: usize, const PAGES: usize>(
+            specs: SPECS,
+            expected: &[ExpectedAction],
+        ) where
+            SPECS: DisplaySpecs<WIDTH, HEIGHT, PAGES>,
+        {
+            DisplayMock::with_expect(expected, |disp_mock| {
+                let mut disp = ST7565::new(disp_mock, specs).into_raw_mode();
+                disp.reset(&mut DummyPin, &mut DummyDelay).unwrap();
+            });
+        }
+
+        check_reset(
+            DOGM128_6,
+            &[
+                Command(&[0b10100010]),
+                Command(&[0b10100001]),
+                Command(&[0b11000000]),
+                Command(&[0b10100110]),
+                Command(&[0b11111000, 0b00000000]),
+                Command(&[0b00100111]),
+                Command(&[0b10000001, 0b00010110]),
+                Command(&[0b00101111]),
+                Command(&[0b01000000]),
+            ],
+        );
+
+        check_reset(
+            DOGL128_6,
+            &[
+                Command(&[0b10100010]),
+                Command(&[0b10100000]),
+                Command(&[0b11001000]),
+                Command(&[0b10100110]),
+                Command(&[0b11111000, 0b00000000]),
+                Command(&[0b00100111]),
+                Command(&[0b10000001, 0b00010110]),
+                Command(&[0b00101111]),
+                Command(&[0b01000000]),
+            ],
+        );
+
+        // The first row is connected to the 32nd common output
+        let expected = [
+            Command(&[0b10100011]),
+            Command(&[0b10100000]),
+            Command(&[0b11000000]),
+            Command(&[0b10100110]),
+            Command(&[0b11111000, 0b00000000]),
+            Command(&[0b00100110]),
+            Command(&[0b10000001, 0b00011000]),
+            Command(&[0b00101111]),
+            Command(&[0b01100000]),
+            Command(&[0b01101000]),
+        ];
+        DisplayMock::with_expect(&expected, |disp_mock| {
+            let mut disp = ST7565::new(disp_mock, LM6059).into_raw_mode();
+            disp.reset(&mut DummyPin, &mut DummyDelay).unwrap();
+            disp.set_line_offset(8).unwrap();
+        });
+
+        #[cfg(feature = "emulator")]
+        {
+            use crate::emulator::Emulator;
+            use display_interface::{DataFormat::U8, WriteOnlyDataCommand};
+            use embedded_graphics::{pixelcolor::BinaryColor, prelude::*};
+
+            // The page map and bit order of the Adafruit library, with the rows scanned in reverse
+            let mut emulator = Emulator::new(LM6059);
+            let adafruit_init = [
+                0b10100011, 0b10100000, 0b11001000, 0b01000000, 0b00101111, 0b10101111,
+            ];
+            emulator.send_commands(U8(&adafruit_init)).unwrap();
+            let page_map = [3, 2, 1, 0, 7, 6, 5, 4];
+            for (x, y) in [(0, 0), (5, 40)] {
+                emulator
+                    .send_commands(U8(&[0b10110000 | page_map[y / 8], 0b00010000, x as u8 + 1]))
+                    .unwrap();
+                emulator.send_data(U8(&[1 << (7 - y % 8)])).unwrap();
+            }
+            for y in 0..64 {
+                for x in 0..128 {
+                    let expected = (x, y) == (0, 0) || (x, y) == (5, 40);
+                    assert_eq!(emulator.pixel(x, y), expected, "Pixel ({}, {})", x, y);
+                }
+            }
+
+            // The driver shows the same picture
+            let mut emulator = Emulator::new(LM6059);
+            let mut buffer = GraphicsPageBuffer::new();
+            let mut disp = ST7565::new(&mut emulator, LM6059).into_graphics_mode(&mut buffer);
+            disp.reset(&mut DummyPin, &mut DummyDelay).unwrap();
+            disp.set_display_on(true).unwrap();
+            Pixel(Point::new(0, 0), BinaryColor::On)
+                .draw(&mut disp)
+                .unwrap();
+            Pixel(Point::new(5, 40), BinaryColor::On)
+                .draw(&mut disp)
+                .unwrap();
+            disp.flush().unwrap();
+            for y in 0..64 {
+                for x in 0..128 {
+                    let expected = (x, y) == (0, 0) || (x, y) == (5, 40);
+                    assert_eq!(emulator.pixel(x, y), expected, "Pixel ({}, {})", x, y);
+                }
+            }
+        }
+
+        check_reset(
+            NHD_C12832A1Z,
+            &[
+                Command(&[0b10100010]),
+                Command(&[0b10100000]),
+                Command(&[0b11001000]),
+                Command(&[0b10100110]),
+                Command(&[0b11111000, 0b00000000]),
+                Command(&[0b00100010]),
+                Command(&[0b10000001, 0b00010111]),
+                Command(&[0b00101111]),
+                Command(&[0b01000000]),
+            ],
+        );
+
+        check_reset(
+            NHD_C12864,
+            &[
+                Command(&[0b10100010]),
+                Command(&[0b10100000]),
+                Command(&[0b11001000]),
+                Command(&[0b10100110]),
+                Command(&[0b11111000, 0b00000000]),
+                Command(&[0b00100110]),
+                Command(&[0b10000001, 0b00010001]),
+                Command(&[0b00101111]),
+                Command(&[0b01000000]),
+            ],
+        );
+
+        check_reset(
+            JLX12864,
+            &[
+                Command(&[0b10100010]),
+                Command(&[0b10100000]),
+                Command(&[0b11001000]),
+                Command(&[0b10100110]),
+                Command(&[0b11111000, 0b00000000]),
+                Command(&[0b00100011]),
+                Command(&[0b10000001, 0b00101000]),
+                Command(&[0b00101111]),
+                Command(&[0b01000000]),
+            ],
+        );
+
+        check_reset(
+            ERC12864,
+            &[
+                Command(&[0b10100010]),
+                Command(&[0b10100001]),
+                Command(&[0b11000000]),
+                Command(&[0b10100110]),
+                Command(&[0b11111000, 0b00000000]),
+                Command(&[0b00100011]),
+                Command(&[0b10000001, 0b00100111]),
+                Command(&[0b00101111]),
+                Command(&[0b01000000]),
+            ],
+        );
+    }
+
+    #[test]
+    fn row_offset() {
+        use super::dummy_hal::{DummyDelay, DummyPin};
+        use crate::{
+            types::{BoosterRatio, PowerControlMode},
+            DisplaySpecs,
+        };
+
+        // The first row is connected to the 8th common output
+        struct RowOffsetDisplay;
+        impl DisplaySpecs<128, 64, 8> for RowOffsetDisplay {
+            const FLIP_ROWS: bool = true;
+            const FLIP_COLUMNS: bool = false;
+            const INVERTED: bool = false;
+            const BIAS_MODE_1: bool = false;
+            const POWER_CONTROL: PowerControlMode = PowerControlMode {
+                booster_circuit: true,
+                voltage_regulator_circuit: true,
+                voltage_follower_circuit: true,
+            };
+            const VOLTAGE_REGULATOR_RESISTOR_RATIO: u8 = 0b011;
+            const ELECTRONIC_VOLUME: u8 = 0b011111;
+            const BOOSTER_RATIO: BoosterRatio = BoosterRatio::StepUp2x3x4x;
+            const COLUMN_OFFSET: u8 = 0;
+            const ROW_OFFSET: u8 = 8;
+        }
+
+        let expected = [
+            Command(&[0b10100010]),
+            Command(&[0b10100000]),
+            Command(&[0b11001000]),
+            Command(&[0b10100110]),
+            Command(&[0b11111000, 0b00000000]),
+            Command(&[0b00100011]),
+            Command(&[0b10000001, 0b00011111]),
+            Command(&[0b00101111]),
+            Command(&[0b01111000]),
+            Command(&[0b01111011]),
+        ];
+        DisplayMock::with_expect(&expected, |disp_mock| {
+            let mut disp = ST7565::new(disp_mock, RowOffsetDisplay).into_raw_mode();
+            disp.reset(&mut DummyPin, &mut DummyDelay).unwrap();
+            disp.set_line_offset(3).unwrap();
+        });
+
+        #[cfg(feature = "emulator")]
+        {
+            use crate::{emulator::Emulator, types::Orientation};
+            use embedded_graphics::{pixelcolor::BinaryColor, prelude::*};
+
+            let mut emulator = Emulator::new(RowOffsetDisplay);
+            let mut buffer = GraphicsPageBuffer::new();
+            let mut disp =
+                ST7565::new(&mut emulator, RowOffsetDisplay).into_graphics_mode(&mut buffer);
+            disp.reset(&mut DummyPin, &mut DummyDelay).unwrap();
+            disp.set_display_on(true).unwrap();
+            Pixel(Point::new(3, 0), BinaryColor::On)
+                .draw(&mut disp)
+                .unwrap();
+            disp.flush().unwrap();
+            let (disp, emulator) = disp.release_display_interface();
+            for y in 0..64 {
+                for x in 0..128 {
+                    assert_eq!(
+                        emulator.pixel(x, y),
+                        (x, y) == (3, 0),
+                        "Pixel ({}, {})",
+                        x,
+                        y
+                    );
+                }
+            }
+
+            let mut disp = disp.attach_display_interface(emulator);
+            disp.set_orientation(Orientation::UpsideDown).unwrap();
+            disp.flush().unwrap();
+            let (_, emulator) = disp.release_display_interface();
+            for y in 0..64 {
+                for x in 0..128 {
+                    assert_eq!(
+                        emulator.pixel(x, y),
+                        (x, y) == (124, 63),
+                        "Pixel ({}, {})",
+                        x,
+                        y
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn runtime_settings() {
+        use super::dummy_hal::{DummyDelay, DummyPin};
+        use crate::types::{BoosterRatio, Contrast, LcdBias, PowerControlMode, RegulatorRatio};
+
+        assert_eq!(Contrast::new(63).map(Contrast::value), Some(63));
+        assert_eq!(Contrast::new(64), None);
+        assert_eq!(RegulatorRatio::new(7).map(RegulatorRatio::value), Some(7));
+        assert_eq!(RegulatorRatio::new(8), None);
+
+        let power_control = PowerControlMode {
+            booster_circuit: false,
+            voltage_regulator_circuit: true,
+            voltage_follower_circuit: true,
+        };
+
+        let expected = [
+            Command(&[0b10000001, 0b00101010]),
+            Command(&[0b00100101]),
+            Command(&[0b10100011]),
+            Command(&[0b11111000, 0b00000011]),
+            Command(&[0b00101011]),
+            // Reset restores the user settings
+            Command(&[0b10100011]),
+            Command(&[0b10100001]),
+            Command(&[0b11000000]),
+            Command(&[0b10100110]),
+            Command(&[0b11111000, 0b00000011]),
+            Command(&[0b00100101]),
+            Command(&[0b10000001, 0b00101010]),
+            Command(&[0b00101011]),
+            Command(&[0b01000000]),
+        ];
+        DisplayMock::with_expect(&expected, |disp_mock| {
+            let mut disp = ST7565::new(disp_mock, DOGM132W5).into_raw_mode();
+            assert_eq!(disp.contrast().value(), 0b011111);
+
+            disp.set_contrast(Contrast::new(42).unwrap()).unwrap();
+            disp.set_regulator_ratio(RegulatorRatio::new(5).unwrap())
+                .unwrap();
+            disp.set_bias(LcdBias::Mode1).unwrap();
+            disp.set_booster_ratio(BoosterRatio::StepUp6x).unwrap();
+            disp.set_power_control(power_control).unwrap();
+
+            assert_eq!(disp.contrast().value(), 42);
+            assert_eq!(disp.regulator_ratio().value(), 5);
+            assert_eq!(disp.bias(), LcdBias::Mode1);
+            assert_eq!(disp.booster_ratio(), BoosterRatio::StepUp6x);
+            assert_eq!(disp.power_control(), power_control);
+
+            disp.reset(&mut DummyPin, &mut DummyDelay).unwrap();
+        });
+    }
+
+    #[test]
+    fn power_save() {
+        use crate::types::{PowerControlMode, PowerState, StaticIndicatorMode};
+
+        let expected = [
+            Command(&[0b10101111]),
+            // Sleep
+            Command(&[0b10101100]),
+            Command(&[0b10101110]),
+            Command(&[0b10100101]),
+            // Wake
+            Command(&[0b00101111]),
+            Command(&[0b10100100]),
+            Command(&[0b10101111]),
+            // Standby
+            Command(&[0b10101101, 0b00000001]),
+            Command(&[0b10101110]),
+            Command(&[0b10100101]),
+            // Wake
+            Command(&[0b00101110]),
+            Command(&[0b10100100]),
+            Command(&[0b10101110]),
+            // Power off
+            Command(&[0b10101100]),
+            Command(&[0b10101110]),
+            Command(&[0b10100101]),
+            Command(&[0b00101010]),
+            Command(&[0b00101000]),
+        ];
+        DisplayMock::with_expect(&expected, |disp_mock| {
+            let mut disp = ST7565::new(disp_mock, DOGM132W5).into_raw_mode();
+            disp.set_display_on(true).unwrap();
+
+            disp.enter_sleep().unwrap();
+            assert_eq!(disp.power_state(), PowerState::Sleep);
+            // Does not turn the display back on while sleeping
+            disp.set_display_on(true).unwrap();
+            disp.wake().unwrap();
+            assert_eq!(disp.power_state(), PowerState::Active);
+
+            disp.enter_standby(StaticIndicatorMode::BlinkSlow).unwrap();
+            assert_eq!(disp.power_state(), PowerState::Standby);
+            disp.set_display_on(false).unwrap();
+            // Only applied when waking up
+            disp.set_power_control(PowerControlMode {
+                booster_circuit: true,
+                voltage_regulator_circuit: true,
+                voltage_follower_circuit: false,
+            })
+            .unwrap();
+            disp.wake().unwrap();
+
+            disp.power_off().unwrap();
+            assert_eq!(disp.power_state(), PowerState::Off);
+        });
+    }
+
+    #[test]
+    fn controller_variants() {
+        use super::dummy_hal::{DummyDelay, DummyPin};
+        use crate::{
+            command::Command,
+            displays::{S6B1713_12864, ST7567A_12864},
+            types::{BoosterRatio, Controller, PowerState, StaticIndicatorMode},
+        };
+        use display_interface::DisplayError;
+
+        let indicator = Command::StaticIndicatorSet { mode: None };
+        let booster_6x = Command::BoosterRatioSet {
+            stepup_value: BoosterRatio::StepUp6x,
+        };
+        assert!(indicator.encode_for(Controller::St7565).is_some());
+        assert!(booster_6x.encode_for(Controller::St7565).is_some());
+        assert_eq!(indicator.encode_for(Controller::St7567), None);
+        assert_eq!(booster_6x.encode_for(Controller::St7567), None);
+
+        let expected = [
+            Command(&[0b10100010]),
+            Command(&[0b10100000]),
+            Command(&[0b11001000]),
+            Command(&[0b10100110]),
+            Command(&[0b11111000, 0b00000000]),
+            Command(&[0b00100101]),
+            Command(&[0b10000001, 0b00100000]),
+            Command(&[0b00101111]),
+            Command(&[0b01000000]),
+            Command(&[0b10100100]),
+            Command(&[0b10101110]),
+            // The icon line gets cleared
+            Command(&[0b10111000, 0b00010000, 0b00000000]),
+            Data(&[0; 132]),
+            // Sleep, without turning off the static indicator
+            Command(&[0b10101110]),
+            Command(&[0b10100101]),
+        ];
+        DisplayMock::with_expect(&expected, |disp_mock| {
+            let mut disp = ST7565::new(disp_mock, ST7567A_12864).into_raw_mode();
+            disp.reset(&mut DummyPin, &mut DummyDelay).unwrap();
+
+            assert!(matches!(
+                disp.set_booster_ratio(BoosterRatio::StepUp6x),
+                Err(DisplayError::InvalidFormatError)
+            ));
+            assert_eq!(disp.booster_ratio(), BoosterRatio::StepUp2x3x4x);
+            assert!(matches!(
+                disp.set_static_indicator(None),
+                Err(DisplayError::InvalidFormatError)
+            ));
+            assert!(matches!(
+                disp.enter_standby(StaticIndicatorMode::On),
+                Err(DisplayError::InvalidFormatError)
+            ));
+            assert_eq!(disp.power_state(), PowerState::Active);
+
+            disp.enter_sleep().unwrap();
+        });
+
+        let expected = [
+            Command(&[0b11100010]),
+            Command(&[0b10100010]),
+            Command(&[0b10100000]),
+            Command(&[0b11001000]),
+            Command(&[0b10100110]),
+            Command(&[0b00100100]),
+            Command(&[0b10000001, 0b00100000]),
+            Command(&[0b00101111]),
+            Command(&[0b01000000]),
+            Command(&[0b10101100]),
+            Command(&[0b10100100]),
+            Command(&[0b10101110]),
+            // Standby
+            Command(&[0b10101101, 0b00000011]),
+            Command(&[0b10101110]),
+            Command(&[0b10100101]),
+        ];
+        DisplayMock::with_expect(&expected, |disp_mock| {
+            let mut disp = ST7565::new(disp_mock, S6B1713_12864).into_raw_mode();
+            disp.reset(&mut DummyPin, &mut DummyDelay).unwrap();
+
+            assert!(matches!(
+                disp.set_booster_ratio(BoosterRatio::StepUp5x),
+                Err(DisplayError::InvalidFormatError)
+            ));
+            disp.enter_standby(StaticIndicatorMode::On).unwrap();
+        });
+    }
+
+    #[test]
+    fn grayscale_mode() {
+        use crate::{displays::ST7571_128128, GrayscalePageBuffer};
+        use embedded_graphics::{
+            pixelcolor::{Gray2, GrayColor},
+            prelude::*,
+        };
+
+        use super::dummy_hal::{DummyDelay, DummyPin};
+
+        let mut page_buffer = GrayscalePageBuffer::new();
+
+        let reset = [
+            Command(&[0b10100000]),
+            Command(&[0b11001000]),
+            Command(&[0b10100110]),
+            Command(&[0b00100111]),
+            Command(&[0b10000001, 0b00110100]),
+            Command(&[0b10101011]),
+            Command(&[0b01111011, 0b00010000, 0b00000000]),
+            Command(&[0b00101111]),
+            Command(&[0b01000000, 0b00000000]),
+        ];
+        // The initial flush sends all 16 pages, with two bytes per column
+        let page_commands: [[u8; 3]; 16] =
+            core::array::from_fn(|page| [0b10110000 | page as u8, 0b00010000, 0b00000000]);
+        let empty_page = [0u8; 256];
+        let update = [
+            // Only the changed columns get sent
+            Command(&[0b10110001, 0b00010000, 0b00000101]),
+            Data(&[0b00000100, 0b00000000, 0b00000000, 0b00000100]),
+            // Light gray gets brighter
+            Command(&[0b10001010, 0x33, 0b10001011, 0x33]),
+        ];
+
+        let mut expected = [Data(&[]); 9 + 2 * 16 + 3];
+        expected[..9].copy_from_slice(&reset);
+        for (page, command) in page_commands.iter().enumerate() {
+            expected[9 + 2 * page] = Command(command);
+            expected[10 + 2 * page] = Data(&empty_page);
+        }
+        expected[9 + 2 * 16..].copy_from_slice(&update);
+
+        DisplayMock::with_expect(&expected, |disp_mock| {
+            let mut disp =
+                ST7565::new(disp_mock, ST7571_128128).into_grayscale_mode(&mut page_buffer);
+            disp.reset(&mut DummyPin, &mut DummyDelay).unwrap();
+            disp.flush().unwrap();
+
+            disp.draw_iter([
+                Pixel(Point::new(5, 10), Gray2::new(2)),
+                Pixel(Point::new(6, 10), Gray2::new(1)),
+                Pixel(Point::new(200, 10), Gray2::BLACK),
+            ])
+            .unwrap();
+            disp.flush().unwrap();
+
+            disp.set_gray_level(Gray2::new(2), 3).unwrap();
+        });
+    }
+
+    #[test]
+    fn emulator() {
+        #[cfg(feature = "emulator")]
+        {
+            use super::dummy_hal::{DummyDelay, DummyPin};
+            use crate::{displays::DOGL128_6_EXT12V, emulator::Emulator};
+            use embedded_graphics::{
+                pixelcolor::BinaryColor,
+                prelude::*,
+                primitives::{PrimitiveStyle, Rectangle},
+            };
+
+            fn is_in_rectangle(x: usize, y: usize) -> bool {
+                (10..20).contains(&x) && (5..12).contains(&y)
+            }
+
+            // Upright display
+            let mut emulator = Emulator::new(DOGL128_6_EXT12V);
+            let mut buffer = GraphicsPageBuffer::new();
+            let mut disp =
+                ST7565::new(&mut emulator, DOGL128_6_EXT12V).into_graphics_mode(&mut buffer);
+            disp.reset(&mut DummyPin, &mut DummyDelay).unwrap();
+            Rectangle::new(Point::new(10, 5), Size::new(10, 7))
+                .into_styled(PrimitiveStyle::with_fill(BinaryColor::On))
+                .draw(&mut disp)
+                .unwrap();
+            disp.flush().unwrap();
+            assert!(!emulator.display_on());
+            assert_eq!(emulator.pixels(), [[false; 128]; 64]);
+
+            let mut disp = ST7565::new(&mut emulator, DOGL128_6_EXT12V).into_raw_mode();
+            disp.set_display_on(true).unwrap();
+            let pixels = emulator.pixels();
+            for (y, row) in pixels.iter().enumerate() {
+                for (x, &pixel) in row.iter().enumerate() {
+                    assert_eq!(pixel, is_in_rectangle(x, y), "Pixel ({}, {})", x, y);
+                }
+            }
+
+            // Flipped ADC and COM directions rotate the display by 180 degrees
+            let mut disp = ST7565::new(&mut emulator, DOGL128_6_EXT12V).into_raw_mode();
+            disp.adc_select(true).unwrap();
+            disp.common_output_mode_select(false).unwrap();
+            for y in 0..64 {
+                for x in 0..128 {
+                    // Without correcting the column offset, the image gets shifted by 4 pixels
+                    let rotated = x <= 123 && is_in_rectangle(123 - x, 63 - y);
+                    assert_eq!(emulator.pixel(x, y), rotated, "Pixel ({}, {})", x, y);
+                }
+            }
+
+            // Inversion, all points and sleep
+            let mut disp = ST7565::new(&mut emulator, DOGL128_6_EXT12V).into_raw_mode();
+            disp.set_inverted(true).unwrap();
+            assert!(emulator.pixel(0, 0));
+            let mut disp = ST7565::new(&mut emulator, DOGL128_6_EXT12V).into_raw_mode();
+            disp.enter_sleep().unwrap();
+            assert!(emulator.power_save());
+            assert!(!emulator.pixel(0, 0));
+
+            // Register state
+            assert_eq!(emulator.contrast(), 0b010110);
+            assert_eq!(emulator.regulator_ratio(), 0b111);
+            assert_eq!(emulator.power_control(), 0b011);
+            assert_eq!(emulator.ddram()[0][4 + 10], 0b11100000);
+            assert_eq!(emulator.ddram()[1][4 + 10], 0b00001111);
+        }
+    }
+
+    #[test]
+    fn orientation() {
+        use crate::{mock::Recording, types::Orientation};
+
+        let mut recording = Recording::<2048>::new();
+        let mut disp_mock = DisplayMock::recording(&mut recording);
+        let mut buffer = GraphicsPageBuffer::new();
+        let mut disp = ST7565::new(&mut disp_mock, DOGM132W5).into_graphics_mode(&mut buffer);
+        disp.flush().unwrap();
+        disp.set_line_offset(3).unwrap();
+        disp.set_orientation(Orientation::UpsideDown).unwrap();
+        assert_eq!(disp.orientation(), Orientation::UpsideDown);
+        disp.flush().unwrap();
+
+        let empty_line = [0u8; 132];
+        let expected = [
+            Command(&[0b01000011]),
+            Command(&[0b10100000]),
+            Command(&[0b11001000]),
+            Command(&[0b01100011]),
+            Command(&[0b10110000, 0b00010000, 0b00000000]),
+            Data(empty_line.as_slice()),
+        ];
+        assert!(recording.actions().skip(8).take(6).eq(expected));
+
+        #[cfg(feature = "emulator")]
+        {
+            use super::dummy_hal::{DummyDelay, DummyPin};
+            use crate::{displays::DOGL128_6_EXT12V, emulator::Emulator};
+            use embedded_graphics::{
+                pixelcolor::BinaryColor,
+                prelude::*,
+                primitives::{PrimitiveStyle, Rectangle},
+            };
+
+            fn is_in_rectangle(x: usize, y: usize) -> bool {
+                (10..20).contains(&x) && (5..12).contains(&y)
+            }
+
+            let mut emulator = Emulator::new(DOGL128_6_EXT12V);
+            let mut buffer = GraphicsPageBuffer::new();
+            let mut disp =
+                ST7565::new(&mut emulator, DOGL128_6_EXT12V).into_graphics_mode(&mut buffer);
+            disp.reset(&mut DummyPin, &mut DummyDelay).unwrap();
+            disp.set_display_on(true).unwrap();
+            Rectangle::new(Point::new(10, 5), Size::new(10, 7))
+                .into_styled(PrimitiveStyle::with_fill(BinaryColor::On))
+                .draw(&mut disp)
+                .unwrap();
+            disp.set_orientation(Orientation::UpsideDown).unwrap();
+            disp.flush().unwrap();
+
+            // Resets keep the orientation
+            disp.reset(&mut DummyPin, &mut DummyDelay).unwrap();
+            disp.set_display_on(true).unwrap();
+            let (_, emulator) = disp.release_display_interface();
+            for y in 0..64 {
+                for x in 0..128 {
+                    let rotated = is_in_rectangle(127 - x, 63 - y);
+                    assert_eq!(emulator.pixel(x, y), rotated, "Pixel ({}, {})", x, y);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn mock() {
+        use crate::mock::Recording;
+        use display_interface::{
+            DataFormat::{U16BEIter, U8Iter, U16LE},
+            DisplayError, WriteOnlyDataCommand,
+        };
+
+        let expected = [
+            Command(&[0x12, 0x34, 0x56, 0x78]),
+            Data(&[0x12, 0x34]),
+            Data(&[1, 2, 3]),
+        ];
+        DisplayMock::with_expect(&expected, |disp_mock| {
+            disp_mock
+                .send_commands(U16LE(&mut [0x3412, 0x7856]))
+                .unwrap();
+            disp_mock
+                .send_data(U16BEIter(&mut [0x1234].iter().copied()))
+                .unwrap();
+            disp_mock
+                .send_data(U8Iter(&mut [1, 2, 3].iter().copied()))
+                .unwrap();
+        });
+
+        let mut recording = Recording::<16>::new();
+        let mut disp_mock =
+            DisplayMock::recording(&mut recording).fail_at(1, DisplayError::BusWriteError);
+        let mut disp = ST7565::new(&mut disp_mock, DOGM132W5).into_raw_mode();
+        disp.set_display_on(true).unwrap();
+        assert!(disp.set_page(0).is_err());
+
+        assert!(recording
+            .actions()
+            .eq([Command(&[0b10101111]), Command(&[0b10110000]),]));
+    }
+
+    #[test]
+    fn async_graphics_mode() {
+        #[cfg(feature = "async")]
+        {
+            use super::{
+                block_on::block_on,
+                dummy_hal::{DummyDelay, DummyPin},
+            };
+            use embedded_graphics::{pixelcolor::BinaryColor, prelude::*};
+
+            let empty_line = [0u8; 132];
+            let expected = [
+                Command(&[0b10100010]),
+                Command(&[0b10100001]),
+                Command(&[0b11000000]),
+                Command(&[0b10100110]),
+                Command(&[0b11111000, 0b00000000]),
+                Command(&[0b00100011]),
+                Command(&[0b10000001, 0b00011111]),
+                Command(&[0b00101111]),
+                Command(&[0b01000000]),
+                Command(&[0b10110000, 0b00010000, 0b00000000]),
+                Data(empty_line.as_slice()),
+                Command(&[0b10110001, 0b00010000, 0b00000000]),
+                Data(empty_line.as_slice()),
+                Command(&[0b10110010, 0b00010000, 0b00000000]),
+                Data(empty_line.as_slice()),
+                Command(&[0b10110011, 0b00010000, 0b00000000]),
+                Data(empty_line.as_slice()),
+                Command(&[0b10101111]),
+            ];
+            let mut buffer = GraphicsPageBuffer::new();
+            let mut disp = DisplayMock::with_expect(&expected, |disp_mock| {
+                let mut disp =
+                    ST7565::new_async(disp_mock, DOGM132W5).into_graphics_mode(&mut buffer);
+                block_on(async {
+                    disp.reset(&mut DummyPin, &mut DummyDelay).await.unwrap();
+                    disp.flush().await.unwrap();
+                    disp.set_display_on(true).await.unwrap();
+                });
+                disp.release_display_interface().0
+            });
+
+            Pixel(Point::new(3, 9), BinaryColor::On)
+                .draw(&mut disp)
+                .unwrap();
+
+            let expected = [
+                Command(&[0b10110001, 0b00010000, 0b00000011]),
+                Data(&[0b00000010]),
+            ];
+            DisplayMock::with_expect(&expected, |disp_mock| {
+                let mut disp = disp.attach_async_display_interface(disp_mock);
+                block_on(disp.flush()).unwrap();
+            });
+        }
+    }
 }
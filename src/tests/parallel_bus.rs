@@ -0,0 +1,120 @@
+use core::{cell::RefCell, convert::Infallible};
+
+use display_interface::DataFormat::U8;
+use embedded_hal::digital::{ErrorType, InputPin, OutputPin};
+
+use crate::interface::{parallel::Protocol, ReadableDataCommand};
+
+pub const A0: usize = 8;
+pub const WR: usize = 9;
+pub const RD: usize = 10;
+pub const CS: usize = 11;
+
+/// The wires between a parallel interface and a controller.
+///
+/// Forwards the transfers to the controller at the edges of the strobes.
+pub struct Wires<'c, C> {
+    protocol: Protocol,
+    levels: [bool; 12],
+    /// The byte the controller drives onto the data lines
+    output: Option<u8>,
+    controller: &'c mut C,
+}
+
+impl<'c, C> Wires<'c, C>
+where
+    C: ReadableDataCommand,
+{
+    pub fn new(protocol: Protocol, controller: &'c mut C) -> Self {
+        Self {
+            protocol,
+            levels: [true; 12],
+            output: None,
+            controller,
+        }
+    }
+
+    fn set(&mut self, index: usize, level: bool) {
+        let previous = core::mem::replace(&mut self.levels[index], level);
+        if self.levels[CS] {
+            self.output = None;
+            return;
+        }
+
+        let (rising, falling) = (!previous && level, previous && !level);
+        let (write, read_start, read_end) = match self.protocol {
+            Protocol::Intel8080 => (
+                index == WR && rising,
+                index == RD && falling,
+                index == RD && rising,
+            ),
+            Protocol::Motorola6800 => (
+                index == RD && falling && !self.levels[WR],
+                index == RD && rising && self.levels[WR],
+                index == RD && falling,
+            ),
+        };
+
+        if write {
+            let byte = (0..8).fold(0, |byte, bit| byte | ((self.levels[bit] as u8) << bit));
+            if self.levels[A0] {
+                self.controller.send_data(U8(&[byte])).unwrap();
+            } else {
+                self.controller.send_commands(U8(&[byte])).unwrap();
+            }
+        }
+        if read_end {
+            self.output = None;
+        }
+        if read_start {
+            self.output = Some(if self.levels[A0] {
+                let mut data = [0];
+                self.controller.read_data(&mut data).unwrap();
+                data[0]
+            } else {
+                self.controller.read_status().unwrap()
+            });
+        }
+    }
+
+    /// The level of a line; the lines are open drain, so both sides can pull them low
+    fn get(&self, index: usize) -> bool {
+        let driven = match self.output {
+            Some(byte) if index < 8 => byte & (1 << index) != 0,
+            _ => true,
+        };
+        self.levels[index] && driven
+    }
+}
+
+/// A pin that is connected to one of the [`Wires`]
+pub struct WirePin<'w, 'c, C> {
+    pub wires: &'w RefCell<Wires<'c, C>>,
+    pub index: usize,
+}
+
+impl<C> ErrorType for WirePin<'_, '_, C> {
+    type Error = Infallible;
+}
+
+impl<C: ReadableDataCommand> OutputPin for WirePin<'_, '_, C> {
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.wires.borrow_mut().set(self.index, false);
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.wires.borrow_mut().set(self.index, true);
+        Ok(())
+    }
+}
+
+impl<C: ReadableDataCommand> InputPin for WirePin<'_, '_, C> {
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
+        Ok(self.wires.borrow().get(self.index))
+    }
+
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
+        Ok(!self.wires.borrow().get(self.index))
+    }
+}
@@ -1,7 +1,46 @@
 //! A collection of types used in this crate
 
+/// The controller chip of a display
+///
+/// Controllers of the ST7565 family share most of their instruction set,
+/// but differ in some commands and in how they have to be initialized.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum Controller {
+    /// Sitronix ST7565
+    St7565,
+    /// Sitronix ST7567.
+    ///
+    /// Has no static indicator, and its booster only supports 4x and 5x;
+    /// [`BoosterRatio::StepUp2x3x4x`] selects 4x.
+    ///
+    /// Its display RAM is addressed like the one of the ST7565. Of its 65 common outputs,
+    /// the first 64 show the scrollable lines, and the last one the icon line in RAM page 8,
+    /// which gets cleared on reset. As its register defaults differ from the ST7565, the
+    /// all points and display on/off states are sent during initialization as well.
+    St7567,
+    /// UltraChip UC1701 and UC1701X.
+    ///
+    /// Has no static indicator and no configurable booster ratio, but a configurable
+    /// [`TemperatureCompensation`].
+    Uc1701,
+    /// Samsung S6B1713 and the compatible KS0713 and NT7534.
+    ///
+    /// The booster ratio is selected through pins instead of a command. As the register
+    /// defaults after a hardware reset differ from the ST7565, the initialization starts
+    /// with a software reset, and also sends the static indicator, all points and
+    /// display on/off states.
+    S6b1713,
+    /// Sitronix ST7571, with 128 lines and a 4-level grayscale mode.
+    ///
+    /// The LCD bias and the booster ratio keep their reset defaults.
+    St7571,
+}
+
 /// The possible options for the internal booster ratio
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum BoosterRatio {
     /// Use this if booster ratio is 2x, 3x or 4x
     StepUp2x3x4x,
@@ -12,7 +51,8 @@ pub enum BoosterRatio {
 }
 
 /// The configuration of the power control circuit
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct PowerControlMode {
     /// Activate booster circuit
     pub booster_circuit: bool,
@@ -23,7 +63,8 @@ pub struct PowerControlMode {
 }
 
 /// The possible options for the static indicator
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum StaticIndicatorMode {
     /// Off
     Off,
@@ -34,3 +75,152 @@ pub enum StaticIndicatorMode {
     /// Constantly on
     On,
 }
+
+/// The temperature compensation of the LCD voltage, on controllers that support it
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum TemperatureCompensation {
+    /// -0.05% per °C
+    Low,
+    /// -0.11% per °C
+    High,
+}
+
+/// The LCD bias mode
+///
+/// The resulting bias ratio depends on the duty ratio of the chip;
+/// for more information, read the ST7565 reference manual.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum LcdBias {
+    /// Bias mode "0"; 1/9 bias at 1/65 duty
+    Mode0,
+    /// Bias mode "1"; 1/7 bias at 1/65 duty
+    Mode1,
+}
+
+/// The ratio of the internal voltage regulator resistors, in the range `0..=7`
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct RegulatorRatio(u8);
+
+impl RegulatorRatio {
+    /// The largest valid ratio
+    pub const MAX: u8 = 0b111;
+
+    /// Creates a regulator ratio.
+    ///
+    /// Returns `None` if the value is larger than [`MAX`](Self::MAX).
+    pub const fn new(value: u8) -> Option<Self> {
+        if value <= Self::MAX {
+            Some(Self(value))
+        } else {
+            None
+        }
+    }
+
+    pub(crate) const fn new_masked(value: u8) -> Self {
+        Self(value & Self::MAX)
+    }
+
+    /// The raw value of the ratio
+    pub const fn value(self) -> u8 {
+        self.0
+    }
+}
+
+/// The contrast of the display, also called electronic volume, in the range `0..=63`
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Contrast(u8);
+
+impl Contrast {
+    /// The largest valid contrast
+    pub const MAX: u8 = 0b111111;
+
+    /// Creates a contrast value.
+    ///
+    /// Returns `None` if the value is larger than [`MAX`](Self::MAX).
+    pub const fn new(value: u8) -> Option<Self> {
+        if value <= Self::MAX {
+            Some(Self(value))
+        } else {
+            None
+        }
+    }
+
+    pub(crate) const fn new_masked(value: u8) -> Self {
+        Self(value & Self::MAX)
+    }
+
+    /// The raw value of the contrast
+    pub const fn value(self) -> u8 {
+        self.0
+    }
+}
+
+/// The power state of the display
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum PowerState {
+    /// Normal operation
+    Active,
+    /// Power save mode with the static indicator turned off
+    Sleep,
+    /// Power save mode with the static indicator turned on
+    Standby,
+    /// The internal power circuits are shut down; the supply voltage can be removed
+    Off,
+}
+
+/// The orientation of the display, set in hardware
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Orientation {
+    /// As given by the display specification
+    Normal,
+    /// Rotated by 180 degrees
+    UpsideDown,
+}
+
+/// The rotation of the graphics output, clockwise
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Rotation {
+    /// No rotation
+    Rotate0,
+    /// Rotated by 90 degrees; width and height are swapped
+    Rotate90,
+    /// Rotated by 180 degrees
+    Rotate180,
+    /// Rotated by 270 degrees; width and height are swapped
+    Rotate270,
+}
+
+/// The status byte of the controller
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Status {
+    /// The controller is busy and does not accept commands
+    pub busy: bool,
+    /// The mapping of RAM columns to segment drivers is reversed
+    pub adc_reverse: bool,
+    /// The display is turned on
+    pub display_on: bool,
+    /// The controller is being reset
+    pub resetting: bool,
+}
+
+impl Status {
+    /// Decodes a status byte as read from the controller.
+    ///
+    /// Note that the ADC and ON/OFF flags are active low.
+    pub fn from_byte(byte: u8) -> Self {
+        Self {
+            busy: byte & 0b10000000 != 0,
+            adc_reverse: byte & 0b01000000 == 0,
+            display_on: byte & 0b00100000 == 0,
+            resetting: byte & 0b00010000 != 0,
+        }
+    }
+}